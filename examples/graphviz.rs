@@ -1,5 +1,4 @@
-use rustygrad::{Neuron, Value, MLP};
-use uuid::Uuid;
+use rustygrad::{viz, Neuron, Value, MLP};
 
 use petgraph::dot::Dot;
 use petgraph::prelude::{DiGraph, NodeIndex};
@@ -11,11 +10,11 @@ use std::io::Write;
 fn value_to_graph_recursive(
     value: &Value,
     graph: &mut DiGraph<String, String>,
-    node_map: &mut HashMap<Uuid, NodeIndex>,
+    node_map: &mut HashMap<u64, NodeIndex>,
 ) -> NodeIndex {
-    let uuid = value.borrow().uuid;
+    let id = value.borrow().id;
 
-    if let Some(&node_index) = node_map.get(&uuid) {
+    if let Some(&node_index) = node_map.get(&id) {
         return node_index;
     }
 
@@ -24,14 +23,20 @@ fn value_to_graph_recursive(
         value.borrow().data,
         value.borrow().grad
     ));
-    node_map.insert(uuid, node_index);
+    node_map.insert(id, node_index);
 
     for prev_value in value.borrow()._prev.iter() {
         let prev_node_index = value_to_graph_recursive(prev_value, graph, node_map);
         graph.add_edge(
             prev_node_index,
             node_index,
-            value.borrow()._op.clone().unwrap_or_default(),
+            value
+                .borrow()
+                ._op
+                .as_deref()
+                .map(|op| viz::op_info(op).map_or(op, |info| info.symbol))
+                .unwrap_or_default()
+                .to_string(),
         );
     }
 
@@ -49,7 +54,7 @@ fn value_to_graph(value: &Value) -> DiGraph<String, String> {
 
 fn create_graphviz(g: &Value, filename: &str) {
     g.backward();
-    let graph = value_to_graph(&g);
+    let graph = value_to_graph(g);
     let mut dot = format!("{:?}", Dot::new(&graph));
 
     // Hacky way to adjust graphviz output
@@ -76,7 +81,7 @@ fn main() {
     //  And a ReLu layer
     let neuron = Neuron::new(2, true);
     // Output node
-    let g = &neuron.forward(&vec![Value::from(7.0)]);
+    let g = &neuron.forward(&[Value::from(7.0)]);
     create_graphviz(g, "examples/plots/neuron.dot");
 
     // Create a 2x2x1 MLP net:
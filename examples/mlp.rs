@@ -1,4 +1,5 @@
 use rustygrad::load_moons_data;
+use rustygrad::losses;
 use rustygrad::Value;
 use rustygrad::MLP;
 
@@ -31,23 +32,15 @@ fn main() {
     }
 
     // ASCII contour plot
-    let mut grid: Vec<Vec<String>> = Vec::new();
-    let bound = 20;
-    for y in -bound..bound {
-        let mut row: Vec<String> = Vec::new();
-        for x in -bound..bound {
-            let k = &model.forward(vec![
-                Value::from(x as f64 / bound as f64 * 2.0),
-                Value::from(-y as f64 / bound as f64 * 2.0),
-            ])[0];
-            row.push(if k.borrow().data > 0.0 {
-                String::from("*")
-            } else {
-                String::from(".")
-            });
-        }
-        grid.push(row);
-    }
+    let grid: Vec<Vec<&str>> = model
+        .decision_grid(2.0, 40)
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&k| if k > 0.0 { "*" } else { "." })
+                .collect()
+        })
+        .collect();
 
     for row in grid {
         for val in row {
@@ -75,18 +68,12 @@ fn loss(model: &MLP, xs: &[Vec<f64>], ys: &[f64]) -> (Value, f64) {
         .zip(&scores)
         .map(|(yi, scorei)| (1.0 + -yi * scorei).relu())
         .collect();
-    let n: f64 = (&losses).len() as f64;
+    let n: f64 = losses.len() as f64;
     let data_loss: Value = losses.into_iter().sum::<Value>() / n;
 
     // L2 regularization
     let alpha: f64 = 0.0001;
-    let reg_loss: Value = alpha
-        * model
-            .parameters()
-            .iter()
-            .map(|p| p * p)
-            .into_iter()
-            .sum::<Value>();
+    let reg_loss: Value = alpha * losses::sum_of_squares(&model.parameters());
     let total_loss = data_loss + reg_loss;
 
     // also get accuracy
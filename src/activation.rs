@@ -0,0 +1,49 @@
+use crate::Value;
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activation {
+    Linear,
+    ReLU,
+    Tanh,
+    Sigmoid,
+}
+
+impl Activation {
+    /// Builds the activation node for `v`; `Linear` returns `v` itself.
+    pub fn apply(&self, v: &Value) -> Value {
+        match self {
+            Activation::Linear => v.clone(),
+            Activation::ReLU => v.relu(),
+            Activation::Tanh => v.tanh(),
+            Activation::Sigmoid => v.sigmoid(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_f64(&self, x: f64) -> f64 {
+        match self {
+            Activation::Linear => x,
+            Activation::ReLU => x.max(0.0),
+            Activation::Tanh => x.tanh(),
+            Activation::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn from_name(name: &str) -> Option<Activation> {
+        match name {
+            "Linear" => Some(Activation::Linear),
+            "ReLU" => Some(Activation::ReLU),
+            "Tanh" => Some(Activation::Tanh),
+            "Sigmoid" => Some(Activation::Sigmoid),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Activation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
@@ -0,0 +1,17 @@
+use crate::MLP;
+
+/// Fast gradient sign method: nudges every feature of `x` by `epsilon` in the
+/// direction that increases the loss for the true label `target` (in `{-1, 1}`),
+/// i.e. pushes the first output's score away from `target`'s sign.
+pub fn fgsm(model: &MLP, x: &[f64], target: f64, epsilon: f64) -> Vec<f64> {
+    model
+        .input_gradient(x)
+        .iter()
+        .zip(x)
+        .map(|(g, xi)| {
+            // `signum` maps 0.0 to 1.0; features with no influence stay put
+            let sign = if *g == 0.0 { 0.0 } else { g.signum() };
+            xi - epsilon * target.signum() * sign
+        })
+        .collect()
+}
@@ -0,0 +1,54 @@
+use crate::{Activation, Value};
+
+// How close to a bound an output must be to count as saturated
+const SATURATION_MARGIN: f64 = 0.01;
+
+/// Summary of a layer's outputs, for spotting dead or saturated units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActivationStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Population standard deviation
+    pub std: f64,
+    /// Fraction within `0.01` of a bound of `Tanh` (±1) or `Sigmoid` (0, 1);
+    /// always `0` for unbounded activations.
+    pub saturated: f64,
+    /// Fraction of exact zeros from a `ReLU`; always `0` for other activations.
+    pub dead: f64,
+}
+
+impl ActivationStats {
+    /// `activation` is the nonlinearity that produced `values`; it decides
+    /// which of `saturated` and `dead` apply. Panics on an empty slice.
+    pub fn new(values: &[Value], activation: Activation) -> ActivationStats {
+        assert!(!values.is_empty(), "need at least one value");
+        let data: Vec<f64> = values.iter().map(|v| v.borrow().data).collect();
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let fraction =
+            |pred: &dyn Fn(f64) -> bool| data.iter().filter(|&&x| pred(x)).count() as f64 / n;
+
+        let saturated = match activation {
+            Activation::Tanh => fraction(&|x| x.abs() >= 1.0 - SATURATION_MARGIN),
+            Activation::Sigmoid => {
+                fraction(&|x| x <= SATURATION_MARGIN || x >= 1.0 - SATURATION_MARGIN)
+            }
+            Activation::Linear | Activation::ReLU => 0.0,
+        };
+        let dead = match activation {
+            Activation::ReLU => fraction(&|x| x == 0.0),
+            _ => 0.0,
+        };
+
+        ActivationStats {
+            min: data.iter().copied().fold(f64::INFINITY, f64::min),
+            max: data.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean,
+            std: variance.sqrt(),
+            saturated,
+            dead,
+        }
+    }
+}
@@ -1,21 +1,90 @@
-use std::{
+// Unused when the target's `core`, or a `std` linked in for tests, already
+// provides the methods
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+use alloc::{format, rc::Rc, string::String, vec, vec::Vec};
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+#[cfg(not(target_has_atomic = "64"))]
+use core::sync::atomic::AtomicUsize;
+use core::{
     cell::RefCell,
-    collections::HashSet,
     fmt::{self, Debug},
     hash::{Hash, Hasher},
     iter::Sum,
     ops,
-    rc::Rc,
+    sync::atomic::Ordering,
 };
-use uuid::Uuid;
+
+// Without `std` there is no hasher, so graph walks use the `alloc` B-tree
+// collections instead.
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap as Map, BTreeSet as Set};
+#[cfg(feature = "std")]
+use std::collections::{HashMap as Map, HashSet as Set};
+
+// Process-global, so ids stay unique across threads within a run. Much cheaper
+// than a random uuid per node; ids are not stable between runs.
+#[cfg(target_has_atomic = "64")]
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(target_has_atomic = "64")]
+fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+// Known issue: targets without 64-bit atomics (most 32-bit embedded ones) count
+// in a `usize`, which wraps after 2^32 nodes on 32-bit and can then hand out an
+// id that is still alive, merging two nodes in graph walks.
+#[cfg(not(target_has_atomic = "64"))]
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(all(not(target_has_atomic = "64"), target_has_atomic = "ptr"))]
+fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed) as u64
+}
+
+// Targets without atomic read-modify-write (e.g. thumbv6m, riscv32imc) are
+// single-core, so a plain load and store is enough outside of interrupts.
+#[cfg(not(target_has_atomic = "ptr"))]
+fn next_id() -> u64 {
+    let id = NEXT_ID.load(Ordering::Relaxed);
+    NEXT_ID.store(id.wrapping_add(1), Ordering::Relaxed);
+    id as u64
+}
 
 pub struct ValueData {
     pub data: f64,
     pub grad: f64,
-    pub uuid: Uuid,
+    /// Unique within a run; `Hash`/`Eq` on `Value` compare this, not `data`.
+    pub id: u64,
     pub _backward: Option<fn(value: &ValueData)>,
     pub _prev: Vec<Value>,
     pub _op: Option<String>,
+    pub label: Option<String>,
+    pub requires_grad: bool,
+}
+
+/// Subgradient that `Value::relu_with` uses at exactly `x == 0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReluZeroGrad {
+    /// `0`, matching `relu`
+    Zero,
+    /// `0.5`, the midpoint of the subdifferential
+    Half,
+    /// `1`, letting the gradient pass through
+    PassThrough,
+}
+
+impl ReluZeroGrad {
+    fn slope(&self) -> f64 {
+        match self {
+            ReluZeroGrad::Zero => 0.0,
+            ReluZeroGrad::Half => 0.5,
+            ReluZeroGrad::PassThrough => 1.0,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -30,40 +99,57 @@ impl ops::Deref for Value {
 
 impl Hash for Value {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.borrow().uuid.hash(state);
+        self.borrow().id.hash(state);
     }
 }
 
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
-        self.borrow().uuid == other.borrow().uuid
+        self.borrow().id == other.borrow().id
     }
 }
 
 impl Eq for Value {}
 
+#[cfg(not(feature = "std"))]
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// By node id, only so values can key the `alloc` B-tree collections.
+#[cfg(not(feature = "std"))]
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.borrow().id.cmp(&other.borrow().id)
+    }
+}
+
 impl_op_ex!(+ |a: &Value, b: &Value| -> Value {
-    let out = Value::from(a.borrow().data + b.borrow().data);
-    out.borrow_mut()._prev = vec![a.clone(), b.clone()];
-    out.borrow_mut()._op = Some(String::from("+"));
-    out.borrow_mut()._backward = Some(|value: &ValueData| {
-        value._prev[0].borrow_mut().grad += value.grad;
-        value._prev[1].borrow_mut().grad += value.grad;
-    });
-    out
+    Value::from_op(
+        a.borrow().data + b.borrow().data,
+        vec![a.clone(), b.clone()],
+        "+",
+        |value: &ValueData| {
+            value._prev[0].borrow_mut().grad += value.grad;
+            value._prev[1].borrow_mut().grad += value.grad;
+        },
+    )
 });
 
 impl_op_ex!(*|a: &Value, b: &Value| -> Value {
-    let out = Value::from(a.borrow().data * b.borrow().data);
-    out.borrow_mut()._prev = vec![a.clone(), b.clone()];
-    out.borrow_mut()._op = Some(String::from("×"));
-    out.borrow_mut()._backward = Some(|value: &ValueData| {
-        let a_data = value._prev[0].borrow().data;
-        let b_data = value._prev[1].borrow().data;
-        value._prev[0].borrow_mut().grad += b_data * value.grad;
-        value._prev[1].borrow_mut().grad += a_data * value.grad;
-    });
-    out
+    Value::from_op(
+        a.borrow().data * b.borrow().data,
+        vec![a.clone(), b.clone()],
+        "×",
+        |value: &ValueData| {
+            let a_data = value._prev[0].borrow().data;
+            let b_data = value._prev[1].borrow().data;
+            value._prev[0].borrow_mut().grad += b_data * value.grad;
+            value._prev[1].borrow_mut().grad += a_data * value.grad;
+        },
+    )
 });
 
 impl_op_ex_commutative!(+|a: &Value, b: f64| -> Value { a + Value::from(b) });
@@ -71,21 +157,39 @@ impl_op_ex_commutative!(*|a: &Value, b: f64| -> Value { a * Value::from(b) });
 
 impl_op_ex!(-|a: &Value| -> Value { a * (-1.0) });
 impl_op_ex!(-|a: &Value, b: &Value| -> Value { a + (-b) });
-impl_op_ex!(/ |a: &Value, b: &Value| -> Value { a * b.pow(-1.0) });
+impl_op_ex!(/ |a: &Value, b: &Value| -> Value { a.div(b) });
 impl_op_ex!(+= |a: &mut Value, b: &Value| { *a = &*a + b });
 impl_op_ex!(*= |a: &mut Value, b: &Value| { *a = &*a * b });
+impl_op_ex!(+= |a: &mut Value, b: f64| { *a = &*a + b });
+impl_op_ex!(*= |a: &mut Value, b: f64| { *a = &*a * b });
 impl_op_ex!(/ |a: &Value, b: f64| -> Value { a / Value::from(b) });
 impl_op_ex!(/ |a: f64, b: &Value| -> Value { Value::from(a) / b });
 
+#[cfg(feature = "profile")]
+thread_local! {
+    static NODES_CREATED: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Number of `Value` nodes allocated on this thread so far.
+#[cfg(feature = "profile")]
+pub(crate) fn nodes_created() -> usize {
+    NODES_CREATED.with(|n| n.get())
+}
+
 impl ValueData {
     fn new(data: f64) -> ValueData {
+        #[cfg(feature = "profile")]
+        NODES_CREATED.with(|n| n.set(n.get() + 1));
+
         ValueData {
             data,
             grad: 0.0,
-            uuid: Uuid::new_v4(),
+            id: next_id(),
             _backward: None,
             _prev: Vec::new(),
             _op: None,
+            label: None,
+            requires_grad: true,
         }
     }
 }
@@ -103,48 +207,626 @@ impl Debug for Value {
     }
 }
 
+/// Asserts that a `Value`'s `data` is within `eps` (default `1e-4`) of an `f64`.
+#[macro_export]
+macro_rules! assert_value_approx {
+    ($value:expr, $expected:expr) => {
+        $crate::assert_value_approx!($value, $expected, 1e-4)
+    };
+    ($value:expr, $expected:expr, $eps:expr) => {{
+        let (value, expected, eps): (&$crate::Value, f64, f64) = (&$value, $expected, $eps);
+        assert!(
+            value.approx_eq(expected, eps),
+            "assertion failed: {:?} is not within {} of {}",
+            value,
+            eps,
+            expected
+        );
+    }};
+}
+
 impl Value {
     fn new(value: ValueData) -> Value {
         Value(Rc::new(RefCell::new(value)))
     }
 
-    pub fn relu(&self) -> Value {
-        let out = Value::from(self.borrow().data.max(0.0));
-        out.borrow_mut()._prev = vec![self.clone()];
-        out.borrow_mut()._op = Some(String::from("ReLU"));
-        out.borrow_mut()._backward = Some(|value: &ValueData| {
-            value._prev[0].borrow_mut().grad += if value.data > 0.0 { value.grad } else { 0.0 };
-        });
+    /// Builds an interior node: the primitive behind every built-in op, public
+    /// so custom differentiable ops can be defined outside the crate.
+    ///
+    /// During `backward`, `backward` is called once with this node's data after
+    /// its `grad` is final. It should read `value.grad` (and `value.data` or the
+    /// parents' `data` as needed) and *accumulate* (`+=`) the chain-rule
+    /// contribution into each `value._prev[i].grad`, never overwrite it, since a
+    /// parent can feed several nodes. Being a plain `fn`, it cannot capture;
+    /// pass constants as extra leaf parents instead.
+    pub fn from_op(data: f64, prev: Vec<Value>, op: &str, backward: fn(&ValueData)) -> Value {
+        #[cfg(feature = "profile")]
+        crate::ops::record(op);
+
+        let out = Value::from(data);
+        {
+            let mut v = out.borrow_mut();
+            v._prev = prev;
+            v._op = Some(String::from(op));
+            v._backward = Some(backward);
+        }
         out
     }
 
+    /// `from_op` for ops over any number of inputs. `parents` become `_prev`
+    /// in order, so in `backward` `value._prev[i]` is the `i`-th parent and
+    /// `value._prev.len()` the arity. E.g. a single-node sum:
+    ///
+    /// ```
+    /// use rustygrad::{Value, ValueData};
+    ///
+    /// fn sum_backward(value: &ValueData) {
+    ///     for p in &value._prev {
+    ///         p.borrow_mut().grad += value.grad;
+    ///     }
+    /// }
+    ///
+    /// let xs: Vec<Value> = (1..=4).map(|i| Value::from(i as f64)).collect();
+    /// let data = xs.iter().map(|x| x.borrow().data).sum();
+    /// let total = Value::from_nary(data, xs.clone(), "sum", sum_backward);
+    /// total.backward();
+    /// assert_eq!(total.borrow().data, 10.0);
+    /// assert!(xs.iter().all(|x| x.borrow().grad == 1.0));
+    /// ```
+    pub fn from_nary(data: f64, parents: Vec<Value>, op: &str, backward: fn(&ValueData)) -> Value {
+        assert!(!parents.is_empty(), "{} needs at least one parent", op);
+        Value::from_op(data, parents, op, backward)
+    }
+
+    /// Allocates a new leaf with the same `data` (and label), a zero `grad` and no parents.
+    ///
+    /// `.clone()` is shallow: it clones the `Rc`, so both handles point at the
+    /// same node and see each other's `data`/`grad` updates. Use `deep_copy`
+    /// when an independent value is needed.
+    pub fn deep_copy(&self) -> Value {
+        let v = self.borrow();
+        let mut data = ValueData::new(v.data);
+        data.label = v.label.clone();
+        data.requires_grad = v.requires_grad;
+        Value::new(data)
+    }
+
+    /// Deep-copies the whole graph reachable from this node, leaves included.
+    ///
+    /// Nodes shared within the original stay shared within the copy, but no
+    /// node is shared between the two, so backpropagating through one leaves
+    /// the other's grads untouched. Grads start at zero, as with `deep_copy`.
+    pub fn clone_graph(&self) -> Value {
+        self._clone_graph(&mut Map::new())
+    }
+
+    fn _clone_graph(&self, copies: &mut Map<u64, Value>) -> Value {
+        let id = self.borrow().id;
+        if let Some(copy) = copies.get(&id) {
+            return copy.clone();
+        }
+
+        let prev: Vec<Value> = self
+            .borrow()
+            ._prev
+            .iter()
+            .map(|p| p._clone_graph(copies))
+            .collect();
+        let copy = self.deep_copy();
+        {
+            let v = self.borrow();
+            let mut c = copy.borrow_mut();
+            c._prev = prev;
+            c._op = v._op.clone();
+            c._backward = v._backward;
+        }
+        copies.insert(id, copy.clone());
+        copy
+    }
+
+    pub fn approx_eq(&self, other: f64, eps: f64) -> bool {
+        (self.borrow().data - other).abs() <= eps
+    }
+
+    pub fn with_label(self, label: &str) -> Value {
+        self.borrow_mut().label = Some(String::from(label));
+        self
+    }
+
+    /// Reconstructs the infix expression this node computes, e.g. `(a + b) * c`.
+    /// Leaves print their `label`, or their `data` when unlabeled.
+    pub fn expr_string(&self) -> String {
+        self._expr_string(&mut Map::new())
+    }
+
+    fn _expr_string(&self, cache: &mut Map<u64, String>) -> String {
+        let v = self.borrow();
+        if let Some(expr) = cache.get(&v.id) {
+            return expr.clone();
+        }
+
+        let wrap = |child: &Value, cache: &mut Map<u64, String>| {
+            let expr = child._expr_string(cache);
+            let is_infix = matches!(child.borrow()._op.as_deref(), Some("+" | "×" | "/" | "^"));
+            if is_infix {
+                format!("({})", expr)
+            } else {
+                expr
+            }
+        };
+
+        let expr = match (&v.label, v._op.as_deref()) {
+            (Some(label), _) => label.clone(),
+            (None, None) => format!("{}", v.data),
+            (None, Some(op @ ("+" | "×" | "/" | "^"))) if v._prev.len() == 2 => {
+                let lhs = wrap(&v._prev[0], cache);
+                let rhs = wrap(&v._prev[1], cache);
+                match op {
+                    "+" => format!("{} + {}", lhs, rhs),
+                    "×" => format!("{} * {}", lhs, rhs),
+                    "/" => format!("{} / {}", lhs, rhs),
+                    _ => format!("{}^{}", lhs, rhs),
+                }
+            }
+            (None, Some(op)) => {
+                let args: Vec<String> = v._prev.iter().map(|p| p._expr_string(cache)).collect();
+                format!("{}({})", op, args.join(", "))
+            }
+        };
+
+        cache.insert(v.id, expr.clone());
+        expr
+    }
+
+    pub fn relu(&self) -> Value {
+        Value::from_op(
+            self.borrow().data.max(0.0),
+            vec![self.clone()],
+            "ReLU",
+            |value: &ValueData| {
+                value._prev[0].borrow_mut().grad += if value.data > 0.0 { value.grad } else { 0.0 };
+            },
+        )
+    }
+
+    pub fn relu_with(&self, policy: ReluZeroGrad) -> Value {
+        Value::from_op(
+            self.borrow().data.max(0.0),
+            vec![self.clone(), Value::from(policy.slope())],
+            "ReLU",
+            |value: &ValueData| {
+                let x = value._prev[0].borrow().data;
+                let slope = if x > 0.0 {
+                    1.0
+                } else if x == 0.0 {
+                    value._prev[1].borrow().data
+                } else {
+                    0.0
+                };
+                value._prev[0].borrow_mut().grad += slope * value.grad;
+            },
+        )
+    }
+
+    pub fn threshold(&self, theta: f64, value: f64) -> Value {
+        let data = self.borrow().data;
+        Value::from_op(
+            if data > theta { data } else { value },
+            vec![self.clone(), Value::from(theta), Value::from(value)],
+            "Threshold",
+            |value: &ValueData| {
+                let x = value._prev[0].borrow().data;
+                let theta = value._prev[1].borrow().data;
+                value._prev[0].borrow_mut().grad += if x > theta { value.grad } else { 0.0 };
+            },
+        )
+    }
+
+    /// Step function: `0` below zero, `1` above, and `at_zero` at exactly zero.
+    ///
+    /// Not differentiable: the node is kept in the graph but propagates no
+    /// gradient to its input, which suits it for building masks and gates.
+    pub fn heaviside(&self, at_zero: f64) -> Value {
+        let x = self.borrow().data;
+        let step = if x > 0.0 {
+            1.0
+        } else if x < 0.0 {
+            0.0
+        } else {
+            at_zero
+        };
+        Value::from_op(
+            step,
+            vec![self.clone(), Value::from(at_zero)],
+            "Heaviside",
+            |_: &ValueData| {},
+        )
+    }
+
+    /// Simulates `bits`-bit affine quantization: snaps to the grid
+    /// `(q - zero_point) * scale` with `q` an integer in `[0, 2^bits - 1]`.
+    ///
+    /// Backward uses the straight-through estimator: the gradient passes
+    /// unchanged where the input falls inside the representable range, and is
+    /// zero where it was clamped.
+    pub fn fake_quant(&self, scale: f64, zero_point: f64, bits: u32) -> Value {
+        let qmax = (2.0_f64).powi(bits as i32) - 1.0;
+        let q = ((self.borrow().data / scale).round() + zero_point).clamp(0.0, qmax);
+        let prev = vec![
+            self.clone(),
+            Value::from(scale),
+            Value::from(zero_point),
+            Value::from(qmax),
+        ];
+        Value::from_op(
+            (q - zero_point) * scale,
+            prev,
+            "FakeQuant",
+            |value: &ValueData| {
+                let x = value._prev[0].borrow().data;
+                let scale = value._prev[1].borrow().data;
+                let zero_point = value._prev[2].borrow().data;
+                let qmax = value._prev[3].borrow().data;
+                let q = (x / scale).round() + zero_point;
+                if (0.0..=qmax).contains(&q) {
+                    value._prev[0].borrow_mut().grad += value.grad;
+                }
+            },
+        )
+    }
+
     pub fn pow(&self, power: f64) -> Value {
-        let out = Value::from(self.borrow().data.powf(power));
-        out.borrow_mut()._prev = vec![self.clone(), Value::from(power)];
-        out.borrow_mut()._op = Some(String::from("^"));
-        out.borrow_mut()._backward = Some(|value: &ValueData| {
-            let base = value._prev[0].borrow().data;
-            let p = value._prev[1].borrow().data;
-            value._prev[0].borrow_mut().grad += p * base.powf(p - 1.0) * value.grad;
-        });
-        out
+        Value::from_op(
+            self.borrow().data.powf(power),
+            vec![self.clone(), Value::from(power)],
+            "^",
+            |value: &ValueData| {
+                let base = value._prev[0].borrow().data;
+                let p = value._prev[1].borrow().data;
+                value._prev[0].borrow_mut().grad += p * base.powf(p - 1.0) * value.grad;
+            },
+        )
+    }
+
+    /// Same as `pow`, named after `f64::powf` for readers coming from `std`.
+    pub fn powf(&self, power: f64) -> Value {
+        self.pow(power)
+    }
+
+    pub fn tanh(&self) -> Value {
+        Value::from_op(
+            self.borrow().data.tanh(),
+            vec![self.clone()],
+            "tanh",
+            |value: &ValueData| {
+                value._prev[0].borrow_mut().grad += (1.0 - value.data * value.data) * value.grad;
+            },
+        )
+    }
+
+    pub fn sigmoid(&self) -> Value {
+        Value::from_op(
+            1.0 / (1.0 + (-self.borrow().data).exp()),
+            vec![self.clone()],
+            "sigmoid",
+            |value: &ValueData| {
+                value._prev[0].borrow_mut().grad += value.data * (1.0 - value.data) * value.grad;
+            },
+        )
+    }
+
+    pub fn exp(&self) -> Value {
+        Value::from_op(
+            self.borrow().data.exp(),
+            vec![self.clone()],
+            "exp",
+            |value: &ValueData| {
+                value._prev[0].borrow_mut().grad += value.data * value.grad;
+            },
+        )
+    }
+
+    pub fn log(&self) -> Value {
+        Value::from_op(
+            self.borrow().data.ln(),
+            vec![self.clone()],
+            "log",
+            |value: &ValueData| {
+                let x = value._prev[0].borrow().data;
+                value._prev[0].borrow_mut().grad += value.grad / x;
+            },
+        )
+    }
+
+    /// Passes the value through unchanged but blocks backward: nothing below
+    /// this node receives gradient through it. Unlike `deep_copy`, the node
+    /// stays connected, so it shows up in the graph and follows `recompute`.
+    pub fn stop_gradient(&self) -> Value {
+        Value::from_op(
+            self.borrow().data,
+            vec![self.clone()],
+            "StopGradient",
+            |_: &ValueData| {},
+        )
+    }
+
+    /// Linear interpolation through the points `(xs[i], ys[i])`, extrapolating
+    /// the end segments beyond the table. `xs` must be strictly increasing.
+    ///
+    /// The backward uses the slope of the active segment; at a knot that is the
+    /// segment to its left. The table is kept as constant leaves in `_prev`.
+    pub fn piecewise_linear(&self, xs: &[f64], ys: &[f64]) -> Value {
+        assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+        assert!(xs.len() >= 2, "need at least two points");
+        assert!(
+            xs.windows(2).all(|w| w[0] < w[1]),
+            "xs must be strictly increasing"
+        );
+
+        let table: Vec<f64> = xs.iter().chain(ys).copied().collect();
+        let (y, _) = interpolate(self.borrow().data, &table);
+        let prev = core::iter::once(self.clone())
+            .chain(table.into_iter().map(Value::from))
+            .collect();
+        Value::from_op(y, prev, "PiecewiseLinear", |value: &ValueData| {
+            let args: Vec<f64> = value._prev.iter().map(|p| p.borrow().data).collect();
+            let (_, slope) = interpolate(args[0], &args[1..]);
+            value._prev[0].borrow_mut().grad += slope * value.grad;
+        })
+    }
+
+    /// `a * b + c` as a single node, with one backward instead of two.
+    pub fn fma(a: &Value, b: &Value, c: &Value) -> Value {
+        Value::from_op(
+            a.borrow().data.mul_add(b.borrow().data, c.borrow().data),
+            vec![a.clone(), b.clone(), c.clone()],
+            "fma",
+            |value: &ValueData| {
+                let a_data = value._prev[0].borrow().data;
+                let b_data = value._prev[1].borrow().data;
+                value._prev[0].borrow_mut().grad += b_data * value.grad;
+                value._prev[1].borrow_mut().grad += a_data * value.grad;
+                value._prev[2].borrow_mut().grad += value.grad;
+            },
+        )
+    }
+
+    /// Identity in the forward pass; in backward, the gradient passed on to the
+    /// input is clamped to `[-max_abs, max_abs]`. The node's own `grad` stays
+    /// unclamped.
+    pub fn clip_grad(&self, max_abs: f64) -> Value {
+        Value::from_op(
+            self.borrow().data,
+            vec![self.clone(), Value::from(max_abs)],
+            "ClipGrad",
+            |value: &ValueData| {
+                let max_abs = value._prev[1].borrow().data;
+                value._prev[0].borrow_mut().grad += value.grad.clamp(-max_abs, max_abs);
+            },
+        )
+    }
+
+    /// Soft select: `mask * if_true + (1 - mask) * if_false`. With a mask in
+    /// `[0, 1]` this blends the branches, and gradients reach all three inputs.
+    pub fn select(mask: &Value, if_true: &Value, if_false: &Value) -> Value {
+        mask * if_true + (-mask + 1.0) * if_false
+    }
+
+    pub fn atan(&self) -> Value {
+        Value::from_op(
+            self.borrow().data.atan(),
+            vec![self.clone()],
+            "atan",
+            |value: &ValueData| {
+                let x = value._prev[0].borrow().data;
+                value._prev[0].borrow_mut().grad += value.grad / (1.0 + x * x);
+            },
+        )
+    }
+
+    /// Arcsine, `NaN` outside `[-1, 1]`. The derivative `1/√(1-x²)` is
+    /// unbounded at `±1`, so `1-x²` is floored at `f64::EPSILON` to keep the
+    /// gradient finite there.
+    pub fn asin(&self) -> Value {
+        Value::from_op(
+            self.borrow().data.asin(),
+            vec![self.clone()],
+            "asin",
+            |value: &ValueData| {
+                let x = value._prev[0].borrow().data;
+                value._prev[0].borrow_mut().grad += value.grad / inverse_trig_root(x);
+            },
+        )
+    }
+
+    /// Arccosine, with the same domain handling as `asin`.
+    pub fn acos(&self) -> Value {
+        Value::from_op(
+            self.borrow().data.acos(),
+            vec![self.clone()],
+            "acos",
+            |value: &ValueData| {
+                let x = value._prev[0].borrow().data;
+                value._prev[0].borrow_mut().grad -= value.grad / inverse_trig_root(x);
+            },
+        )
+    }
+
+    /// `self / other` as a single node, which is also what the `/` operator builds.
+    ///
+    /// The gradients are `1/b` and `-(a/b)/b`. Dividing the quotient by `b` twice
+    /// instead of forming `b²` (as `pow(-1.0)` would) avoids overflow for tiny `b`.
+    pub fn div(&self, other: &Value) -> Value {
+        Value::from_op(
+            self.borrow().data / other.borrow().data,
+            vec![self.clone(), other.clone()],
+            "/",
+            |value: &ValueData| {
+                let b = value._prev[1].borrow().data;
+                value._prev[0].borrow_mut().grad += value.grad / b;
+                value._prev[1].borrow_mut().grad -= value.data / b * value.grad;
+            },
+        )
+    }
+
+    /// Returns `None` instead of building a node when `other` is zero.
+    ///
+    /// The `/` operator follows `f64` semantics, so a zero denominator yields
+    /// `inf` (or `NaN` for `0 / 0`) in the forward pass.
+    pub fn checked_div(&self, other: &Value) -> Option<Value> {
+        if other.borrow().data == 0.0 {
+            return None;
+        }
+        Some(self / other)
+    }
+
+    /// Overwrites the data of a leaf, e.g. an input, so the graph can be reused
+    /// for the next sample instead of rebuilt. Call `recompute` on the output
+    /// afterwards to refresh every node that depends on it:
+    ///
+    /// ```
+    /// # use rustygrad::Value;
+    /// let x = Value::from(2.0);
+    /// let y = (&x * &x).relu();
+    /// for sample in [3.0, 4.0] {
+    ///     x.set_leaf(sample);
+    ///     y.recompute().unwrap();
+    ///     y.backward();
+    ///     assert_eq!(x.borrow().grad, 2.0 * sample);
+    /// }
+    /// ```
+    ///
+    /// Panics if the node was produced by an op.
+    pub fn set_leaf(&self, data: f64) {
+        let mut v = self.borrow_mut();
+        assert!(
+            v._op.is_none(),
+            "set_leaf called on an interior {:?} node",
+            v._op
+        );
+        v.data = data;
+    }
+
+    /// Re-runs the forward pass of every node reachable from this one in
+    /// topological order, and resets all their grads to zero.
+    ///
+    /// Built-in ops are replayed by name; errors on ops it does not know, such
+    /// as custom ones built with `from_op`.
+    #[allow(clippy::mutable_key_type)]
+    pub fn recompute(&self) -> Result<(), String> {
+        let mut topo: Vec<Value> = vec![];
+        let mut visited: Set<Value> = Set::new();
+        self._build_topo(&mut topo, &mut visited);
+
+        for v in topo {
+            let mut node = v.borrow_mut();
+            node.grad = 0.0;
+            if let Some(op) = node._op.as_deref() {
+                let args: Vec<f64> = node._prev.iter().map(|p| p.borrow().data).collect();
+                node.data =
+                    eval_op(op, &args).ok_or(format!("recompute does not support op {:?}", op))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Resets `grad` on every node reachable from this one.
+    #[allow(clippy::mutable_key_type)]
+    pub(crate) fn zero_grad_graph(&self) {
+        let mut topo: Vec<Value> = vec![];
+        let mut visited: Set<Value> = Set::new();
+        self._build_topo(&mut topo, &mut visited);
+        for v in topo {
+            v.borrow_mut().grad = 0.0;
+        }
+    }
+
+    /// Number of distinct nodes reachable from this one, itself included.
+    #[allow(clippy::mutable_key_type)]
+    pub fn graph_size(&self) -> usize {
+        let mut topo: Vec<Value> = vec![];
+        let mut visited: Set<Value> = Set::new();
+        self._build_topo(&mut topo, &mut visited);
+        topo.len()
     }
 
     pub fn backward(&self) {
+        self.backward_with(1.0);
+    }
+
+    /// Like `backward`, but seeds the root with `seed` instead of `1.0`, e.g. to
+    /// push an externally computed upstream gradient (a vector-Jacobian product).
+    #[allow(clippy::mutable_key_type)]
+    pub fn backward_with(&self, seed: f64) {
         let mut topo: Vec<Value> = vec![];
-        let mut visited: HashSet<Value> = HashSet::new();
+        let mut visited: Set<Value> = Set::new();
         self._build_topo(&mut topo, &mut visited);
-        topo.reverse();
+        self._propagate(&topo, seed);
+    }
+
+    /// Like `backward`, but then detaches every node of the graph from its
+    /// parents (clearing `_prev`, `_op` and `_backward`), turning them all into
+    /// leaves that keep their `data` and `grad`.
+    ///
+    /// The graph holds strong `Rc`s to everything it was built from, so a loop
+    /// that keeps only the loss alive would otherwise keep every intermediate
+    /// node alive too. Use this when the graph will not be reused.
+    #[allow(clippy::mutable_key_type)]
+    pub fn backward_and_free(&self) {
+        let mut topo: Vec<Value> = vec![];
+        let mut visited: Set<Value> = Set::new();
+        self._build_topo(&mut topo, &mut visited);
+        self._propagate(&topo, 1.0);
 
-        self.borrow_mut().grad = 1.0;
         for v in topo {
+            let mut node = v.borrow_mut();
+            node._prev = Vec::new();
+            node._op = None;
+            node._backward = None;
+        }
+    }
+
+    fn _propagate(&self, topo: &[Value], seed: f64) {
+        self.borrow_mut().grad = seed;
+        for v in topo.iter().rev() {
+            // Nodes without upstream gradient contribute nothing. Skipping them
+            // keeps an `inf` local derivative (e.g. `pow(-1.0)` at zero) from
+            // turning into `0 * inf = NaN` and poisoning the rest of the pass.
+            if v.borrow().grad == 0.0 {
+                continue;
+            }
             if let Some(backprop) = v.borrow()._backward {
                 backprop(&v.borrow());
             }
         }
     }
 
-    fn _build_topo(&self, topo: &mut Vec<Value>, visited: &mut HashSet<Value>) {
+    /// Checks that the graph reachable from this node is acyclic.
+    ///
+    /// `_build_topo` silently skips revisited nodes, so a cycle introduced by
+    /// mutating `_prev` would otherwise go unnoticed during `backward`.
+    pub fn validate(&self) -> Result<(), String> {
+        self._validate(&mut Set::new(), &mut Set::new())
+    }
+
+    fn _validate(&self, on_path: &mut Set<u64>, done: &mut Set<u64>) -> Result<(), String> {
+        let id = self.borrow().id;
+        if done.contains(&id) {
+            return Ok(());
+        }
+        if !on_path.insert(id) {
+            return Err(format!("cycle detected through node {:?} ({})", self, id));
+        }
+        for child in self.borrow()._prev.iter() {
+            child._validate(on_path, done)?;
+        }
+        on_path.remove(&id);
+        done.insert(id);
+        Ok(())
+    }
+
+    #[allow(clippy::mutable_key_type)]
+    fn _build_topo(&self, topo: &mut Vec<Value>, visited: &mut Set<Value>) {
         if visited.insert(self.clone()) {
             self.borrow()._prev.iter().for_each(|child| {
                 child._build_topo(topo, visited);
@@ -160,3 +842,87 @@ impl Sum for Value {
         iter.fold(first, |acc, val| acc + val)
     }
 }
+
+impl<'a> Sum<&'a Value> for Value {
+    fn sum<I: Iterator<Item = &'a Value>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+/// Sums `vs` as a balanced tree of `+` nodes instead of a left-to-right chain.
+/// Rounding error then grows with `log n` rather than `n`.
+#[cfg(feature = "std")]
+pub(crate) fn pairwise_sum(vs: &[Value]) -> Value {
+    match vs.len() {
+        0 => panic!("must contain at least one Value"),
+        1 => vs[0].clone(),
+        n => pairwise_sum(&vs[..n / 2]) + pairwise_sum(&vs[n / 2..]),
+    }
+}
+
+// `(y, dy/dx)` of the interpolant through `table = [x0..xn, y0..yn]`
+pub(crate) fn interpolate(x: f64, table: &[f64]) -> (f64, f64) {
+    let (xs, ys) = table.split_at(table.len() / 2);
+    let last = xs.len() - 2;
+    let i = xs[1..=last]
+        .iter()
+        .position(|&knot| x <= knot)
+        .unwrap_or(last);
+    let slope = (ys[i + 1] - ys[i]) / (xs[i + 1] - xs[i]);
+    (ys[i] + slope * (x - xs[i]), slope)
+}
+
+// `√(1-x²)`, floored away from zero at the edges of the asin/acos domain
+pub(crate) fn inverse_trig_root(x: f64) -> f64 {
+    (1.0 - x * x).max(f64::EPSILON).sqrt()
+}
+
+// Forward rule of each built-in op, given its parents' data in `_prev` order
+fn eval_op(op: &str, args: &[f64]) -> Option<f64> {
+    let out = match op {
+        "+" => args[0] + args[1],
+        "×" => args[0] * args[1],
+        "fma" => args[0].mul_add(args[1], args[2]),
+        "/" => args[0] / args[1],
+        "^" => args[0].powf(args[1]),
+        "ReLU" => args[0].max(0.0),
+        "Threshold" if args[0] > args[1] => args[0],
+        "Threshold" => args[2],
+        "Heaviside" if args[0] > 0.0 => 1.0,
+        "Heaviside" if args[0] < 0.0 => 0.0,
+        "Heaviside" => args[1],
+        "FakeQuant" => {
+            let (scale, zero_point, qmax) = (args[1], args[2], args[3]);
+            let q = ((args[0] / scale).round() + zero_point).clamp(0.0, qmax);
+            (q - zero_point) * scale
+        }
+        "StopGradient" | "ClipGrad" => args[0],
+        "PiecewiseLinear" => interpolate(args[0], &args[1..]).0,
+        "tanh" => args[0].tanh(),
+        "sigmoid" => 1.0 / (1.0 + (-args[0]).exp()),
+        "exp" => args[0].exp(),
+        "log" => args[0].ln(),
+        "atan" => args[0].atan(),
+        "asin" => args[0].asin(),
+        "acos" => args[0].acos(),
+        "max" => args.iter().copied().reduce(f64::max)?,
+        _ => return None,
+    };
+    Some(out)
+}
+
+/// `J[i][j] = d outputs[i] / d inputs[j]`.
+///
+/// Runs one full backward pass per output (resetting the graph's grads before
+/// each), so the cost is O(outputs × graph size). Grads are left zeroed afterwards.
+pub fn jacobian(outputs: &[Value], inputs: &[Value]) -> Vec<Vec<f64>> {
+    let mut rows = vec![];
+    for out in outputs {
+        outputs.iter().for_each(|o| o.zero_grad_graph());
+        inputs.iter().for_each(|x| x.borrow_mut().grad = 0.0);
+        out.backward();
+        rows.push(inputs.iter().map(|x| x.borrow().grad).collect());
+    }
+    outputs.iter().for_each(|o| o.zero_grad_graph());
+    rows
+}
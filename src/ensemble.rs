@@ -0,0 +1,22 @@
+use crate::MLP;
+
+/// Averages the `predict` outputs of `models` on `x`, output by output.
+/// Unlike `MLP::average`, which averages weights, the models only need the
+/// same number of outputs. Panics if `models` is empty.
+pub fn predict(models: &[&MLP], x: &[f64]) -> Vec<f64> {
+    assert!(!models.is_empty(), "ensemble needs at least one model");
+    let mut sum = models[0].predict(x);
+    for model in &models[1..] {
+        let out = model.predict(x);
+        assert_eq!(
+            out.len(),
+            sum.len(),
+            "models must have the same number of outputs"
+        );
+        for (s, o) in sum.iter_mut().zip(out) {
+            *s += o;
+        }
+    }
+    let n = models.len() as f64;
+    sum.iter().map(|s| s / n).collect()
+}
@@ -0,0 +1,62 @@
+// The `f64` methods that `std` provides, backed by `libm` in `no_std` builds.
+// Only imported without `std`; with it the inherent methods are used.
+#[allow(dead_code)]
+pub(crate) trait Float {
+    fn exp(self) -> f64;
+    fn ln(self) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn sqrt(self) -> f64;
+    fn tanh(self) -> f64;
+    fn atan(self) -> f64;
+    fn asin(self) -> f64;
+    fn acos(self) -> f64;
+    fn round(self) -> f64;
+    fn mul_add(self, a: f64, b: f64) -> f64;
+}
+
+impl Float for f64 {
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn tanh(self) -> f64 {
+        libm::tanh(self)
+    }
+
+    fn atan(self) -> f64 {
+        libm::atan(self)
+    }
+
+    fn asin(self) -> f64 {
+        libm::asin(self)
+    }
+
+    fn acos(self) -> f64 {
+        libm::acos(self)
+    }
+
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+
+    fn mul_add(self, a: f64, b: f64) -> f64 {
+        libm::fma(self, a, b)
+    }
+}
@@ -0,0 +1,198 @@
+use crate::engine::{interpolate, inverse_trig_root};
+use crate::Value;
+use std::collections::HashMap;
+
+// Perturbation small enough that second-order terms vanish entirely in f64
+const STEP: f64 = 1e-20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+/// `(analytic, numeric)` gradient of `f` w.r.t. each of `params`: the first
+/// from one `backward()`, the second from central differences with step `h`.
+///
+/// `f` builds the output from fresh leaves holding `params`, since every
+/// perturbation needs its own forward pass (two per parameter).
+pub fn grad_check<F: Fn(&[Value]) -> Value>(f: F, params: &[f64], h: f64) -> Vec<(f64, f64)> {
+    let analytic = analytic_grads(&f, params);
+    (0..params.len())
+        .zip(analytic)
+        .map(|(i, a)| (a, central_difference(&f, params, i, h)))
+        .collect()
+}
+
+/// `grad_check`, with the finite-difference passes spread over rayon's thread
+/// pool. `Value` graphs cannot cross threads, so each pass rebuilds the graph
+/// on the thread that runs it.
+#[cfg(feature = "parallel")]
+pub fn grad_check_parallel<F: Fn(&[Value]) -> Value + Sync>(
+    f: F,
+    params: &[f64],
+    h: f64,
+) -> Vec<(f64, f64)> {
+    use rayon::prelude::*;
+
+    let analytic = analytic_grads(&f, params);
+    let numeric: Vec<f64> = (0..params.len())
+        .into_par_iter()
+        .map(|i| central_difference(&f, params, i, h))
+        .collect();
+    analytic.into_iter().zip(numeric).collect()
+}
+
+fn analytic_grads<F: Fn(&[Value]) -> Value>(f: &F, params: &[f64]) -> Vec<f64> {
+    let leaves: Vec<Value> = params.iter().map(|&p| Value::from(p)).collect();
+    f(&leaves).backward();
+    leaves.iter().map(|l| l.borrow().grad).collect()
+}
+
+fn central_difference<F: Fn(&[Value]) -> Value>(f: &F, params: &[f64], i: usize, h: f64) -> f64 {
+    let eval = |delta: f64| {
+        let leaves: Vec<Value> = params
+            .iter()
+            .enumerate()
+            .map(|(j, &p)| Value::from(if j == i { p + delta } else { p }))
+            .collect();
+        let out = f(&leaves).borrow().data;
+        out
+    };
+    (eval(h) - eval(-h)) / (2.0 * h)
+}
+
+/// Derivative of `output` w.r.t. the leaf `input` by complex-step differentiation.
+///
+/// The graph is replayed with `input + i·h`, and `Im(output) / h` is returned.
+/// Unlike finite differences there is no subtractive cancellation, so the result
+/// is accurate to machine precision, which makes it a good cross-check for
+/// `backward()`. Errors on ops the complex replay does not support.
+pub fn complex_step(output: &Value, input: &Value) -> Result<f64, String> {
+    let z = replay(output, input, &mut HashMap::new())?;
+    Ok(z.im / STEP)
+}
+
+fn replay(
+    value: &Value,
+    input: &Value,
+    memo: &mut HashMap<u64, Complex>,
+) -> Result<Complex, String> {
+    let v = value.borrow();
+    if let Some(z) = memo.get(&v.id) {
+        return Ok(*z);
+    }
+
+    let mut args = vec![];
+    for p in v._prev.iter() {
+        args.push(replay(p, input, memo)?);
+    }
+
+    let real = |re: f64| Complex { re, im: 0.0 };
+    let z = match v._op.as_deref() {
+        None if value == input => Complex {
+            re: v.data,
+            im: STEP,
+        },
+        None => real(v.data),
+        Some("+") => Complex {
+            re: args[0].re + args[1].re,
+            im: args[0].im + args[1].im,
+        },
+        Some("×") => Complex {
+            re: args[0].re * args[1].re - args[0].im * args[1].im,
+            im: args[0].re * args[1].im + args[0].im * args[1].re,
+        },
+        Some("fma") => Complex {
+            re: args[0].re * args[1].re - args[0].im * args[1].im + args[2].re,
+            im: args[0].re * args[1].im + args[0].im * args[1].re + args[2].im,
+        },
+        Some("/") => {
+            let (a, b) = (args[0], args[1]);
+            let norm = b.re * b.re + b.im * b.im;
+            Complex {
+                re: (a.re * b.re + a.im * b.im) / norm,
+                im: (a.im * b.re - a.re * b.im) / norm,
+            }
+        }
+        // First-order expansion; exact here since the imaginary part is ~STEP
+        Some("^") => {
+            let (base, p) = (args[0], args[1].re);
+            Complex {
+                re: base.re.powf(p),
+                im: p * base.re.powf(p - 1.0) * base.im,
+            }
+        }
+        Some("ReLU") => {
+            if args[0].re > 0.0 {
+                args[0]
+            } else {
+                real(0.0)
+            }
+        }
+        Some("Threshold") => {
+            if args[0].re > args[1].re {
+                args[0]
+            } else {
+                args[2]
+            }
+        }
+        Some("Heaviside") => real(v.data),
+        Some("StopGradient") => real(args[0].re),
+        // Clipping only alters backward; the true derivative passes through
+        Some("ClipGrad") => args[0],
+        Some("PiecewiseLinear") => {
+            let table: Vec<f64> = args[1..].iter().map(|z| z.re).collect();
+            let (y, slope) = interpolate(args[0].re, &table);
+            Complex {
+                re: y,
+                im: slope * args[0].im,
+            }
+        }
+        Some("max") => *args
+            .iter()
+            .reduce(|a, b| if b.re > a.re { b } else { a })
+            .unwrap(),
+        Some("tanh") => {
+            let t = args[0].re.tanh();
+            Complex {
+                re: t,
+                im: (1.0 - t * t) * args[0].im,
+            }
+        }
+        Some("sigmoid") => {
+            let s = 1.0 / (1.0 + (-args[0].re).exp());
+            Complex {
+                re: s,
+                im: s * (1.0 - s) * args[0].im,
+            }
+        }
+        Some("atan") => Complex {
+            re: args[0].re.atan(),
+            im: args[0].im / (1.0 + args[0].re * args[0].re),
+        },
+        Some("asin") => Complex {
+            re: args[0].re.asin(),
+            im: args[0].im / inverse_trig_root(args[0].re),
+        },
+        Some("acos") => Complex {
+            re: args[0].re.acos(),
+            im: -args[0].im / inverse_trig_root(args[0].re),
+        },
+        Some("exp") => {
+            let scale = args[0].re.exp();
+            Complex {
+                re: scale * args[0].im.cos(),
+                im: scale * args[0].im.sin(),
+            }
+        }
+        Some("log") => Complex {
+            re: args[0].re.hypot(args[0].im).ln(),
+            im: args[0].im.atan2(args[0].re),
+        },
+        Some(op) => return Err(format!("complex step does not support op {:?}", op)),
+    };
+
+    memo.insert(v.id, z);
+    Ok(z)
+}
@@ -0,0 +1,41 @@
+use crate::utils::sample_normal;
+use crate::Value;
+use rand::{distributions::Uniform, rngs::StdRng, Rng, SeedableRng};
+
+/// Samples from `U(-b, b)` with `b = sqrt(6 / nin)`, the He initialization for ReLU nets.
+/// Panics unless `nin > 0`.
+pub fn kaiming_uniform_(params: &[Value], nin: i32) {
+    kaiming_uniform_with_rng_(params, nin, &mut rand::thread_rng());
+}
+
+/// `kaiming_uniform_`, reproducible from `seed` like `normal_`.
+pub fn kaiming_uniform_seeded_(params: &[Value], nin: i32, seed: u64) {
+    kaiming_uniform_with_rng_(params, nin, &mut StdRng::seed_from_u64(seed));
+}
+
+/// `kaiming_uniform_`, drawing from a caller-owned generator.
+pub fn kaiming_uniform_with_rng_<R: Rng>(params: &[Value], nin: i32, rng: &mut R) {
+    assert!(
+        nin > 0,
+        "kaiming_uniform_ needs a positive fan-in, got {}",
+        nin
+    );
+    let bound = (6.0 / nin as f64).sqrt();
+    let range = Uniform::<f64>::new_inclusive(-bound, bound);
+    for p in params {
+        p.borrow_mut().data = rng.sample(range);
+    }
+}
+
+pub fn zeros_(params: &[Value]) {
+    for p in params {
+        p.borrow_mut().data = 0.0;
+    }
+}
+
+pub fn normal_(params: &[Value], mean: f64, std: f64, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    for p in params {
+        p.borrow_mut().data = sample_normal(&mut rng, mean, std);
+    }
+}
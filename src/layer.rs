@@ -1,8 +1,9 @@
-use crate::{Neuron, Value};
+use crate::{Activation, Neuron, Value};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 #[derive(Debug)]
 pub struct Layer {
-    neurons: Vec<Neuron>,
+    pub(crate) neurons: Vec<Neuron>,
 }
 
 impl Layer {
@@ -12,15 +13,117 @@ impl Layer {
         }
     }
 
+    pub fn with_activation(nin: i32, nout: i32, activation: Activation) -> Layer {
+        Layer::with_rng(nin, nout, activation, &mut rand::thread_rng())
+    }
+
+    pub fn with_rng<R: Rng>(nin: i32, nout: i32, activation: Activation, rng: &mut R) -> Layer {
+        Layer {
+            neurons: (0..nout)
+                .map(|_| Neuron::with_rng(nin, activation, rng))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn from_neurons(neurons: Vec<Neuron>) -> Layer {
+        Layer { neurons }
+    }
+
+    pub fn linear(nin: i32, nout: i32) -> Layer {
+        Layer::new(nin, nout, false)
+    }
+
     pub fn from(nin: i32) -> Neuron {
         Neuron::new(nin, true)
     }
 
-    pub fn forward(&self, x: &Vec<Value>) -> Vec<Value> {
+    pub fn forward(&self, x: &[Value]) -> Vec<Value> {
         self.neurons.iter().map(|n| n.forward(x)).collect()
     }
 
+    /// Training-mode forward with inverted dropout: each neuron is dropped with
+    /// probability `dropout_p` (reproducibly for a given seed) and the survivors
+    /// are scaled by `1 / (1 - dropout_p)`. Dropped neurons output a constant
+    /// zero without building their activation. Equals `forward` at `dropout_p == 0`.
+    pub fn forward_train(&self, x: &[Value], dropout_p: f64, seed: u64) -> Vec<Value> {
+        self.forward_train_with_rng(x, dropout_p, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// `forward_train`, drawing the dropout mask from a caller-owned generator.
+    pub fn forward_train_with_rng<R: Rng>(
+        &self,
+        x: &[Value],
+        dropout_p: f64,
+        rng: &mut R,
+    ) -> Vec<Value> {
+        assert!(
+            (0.0..1.0).contains(&dropout_p),
+            "dropout_p must be in [0, 1), got {}",
+            dropout_p
+        );
+        if dropout_p == 0.0 {
+            return self.forward(x);
+        }
+
+        let scale = 1.0 / (1.0 - dropout_p);
+        self.neurons
+            .iter()
+            .map(|n| {
+                if rng.gen::<f64>() < dropout_p {
+                    Value::from(0.0)
+                } else {
+                    n.forward(x) * scale
+                }
+            })
+            .collect()
+    }
+
+    pub fn nout(&self) -> i32 {
+        self.neurons.len() as i32
+    }
+
+    /// Copies the weights out as an `nout × nin` matrix, one row per neuron.
+    pub fn weights(&self) -> Vec<Vec<f64>> {
+        self.neurons
+            .iter()
+            .map(|n| n.w.iter().map(|w| w.borrow().data).collect())
+            .collect()
+    }
+
+    pub fn biases(&self) -> Vec<f64> {
+        self.neurons.iter().map(|n| n.b.borrow().data).collect()
+    }
+
+    /// Returns the outputs plus which neurons fired (pre-activation > 0).
+    pub fn forward_with_mask(&self, x: &[Value]) -> (Vec<Value>, Vec<bool>) {
+        self.neurons
+            .iter()
+            .map(|n| {
+                let (preact, out) = n.forward_with_preact(x);
+                let fired = preact.borrow().data > 0.0;
+                (out, fired)
+            })
+            .unzip()
+    }
+
+    pub fn parameters_iter(&self) -> impl Iterator<Item = &Value> {
+        self.neurons.iter().flat_map(|n| n.parameters_iter())
+    }
+
     pub fn parameters(&self) -> Vec<Value> {
         self.neurons.iter().flat_map(|n| n.parameters()).collect()
     }
+
+    /// Marks every parameter as not requiring grad, so optimizers skip it.
+    pub fn freeze(&self) {
+        for p in self.parameters_iter() {
+            p.borrow_mut().requires_grad = false;
+        }
+    }
+
+    pub fn unfreeze(&self) {
+        for p in self.parameters_iter() {
+            p.borrow_mut().requires_grad = true;
+        }
+    }
 }
@@ -1,17 +1,129 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 #[macro_use]
-extern crate impl_ops;
+mod macros;
+
+mod activation;
+pub use crate::activation::Activation;
 
 mod engine;
-pub use crate::engine::Value;
+pub use crate::engine::{jacobian, ReluZeroGrad, Value, ValueData};
+
+#[cfg(not(feature = "std"))]
+mod float;
 
+#[cfg(feature = "std")]
 mod neuron;
+#[cfg(feature = "std")]
 pub use crate::neuron::Neuron;
 
+#[cfg(feature = "std")]
 mod layer;
+#[cfg(feature = "std")]
 pub use crate::layer::Layer;
 
+#[cfg(feature = "std")]
 mod mlp;
-pub use crate::mlp::MLP;
+#[cfg(feature = "profile")]
+pub use crate::mlp::Profile;
+#[cfg(feature = "std")]
+pub use crate::mlp::{MlpBuilder, MLP};
 
+#[cfg(feature = "std")]
+mod norm;
+#[cfg(feature = "std")]
+pub use crate::norm::{LayerNorm, WeightNorm};
+
+#[cfg(feature = "std")]
+pub use rand::rngs::StdRng;
+
+#[cfg(feature = "std")]
 mod utils;
-pub use crate::utils::{load_moons_data, read_csv_file, DataPoint};
+#[cfg(feature = "std")]
+pub use crate::utils::{
+    csv_rows, jitter, load_moons_data, mixup, polynomial_features, read_csv_file, rotate2d,
+    seeded_rng, shuffle_in_unison, subsample, DataPoint,
+};
+
+#[cfg(feature = "std")]
+mod optim;
+#[cfg(feature = "std")]
+pub use crate::optim::{
+    add_grad_noise, lerp_data_into, Adagrad, Adam, Optimizer, RMSProp, Swa, SGD,
+};
+
+#[cfg(feature = "std")]
+mod schedule;
+#[cfg(feature = "std")]
+pub use crate::schedule::{LearningRate, Scheduler, Warmup};
+
+#[cfg(feature = "std")]
+mod json;
+
+#[cfg(feature = "std")]
+mod serialize;
+
+#[cfg(feature = "std")]
+mod preprocessing;
+#[cfg(feature = "std")]
+pub use crate::preprocessing::{MinMaxScaler, StandardScaler};
+
+#[cfg(feature = "std")]
+mod training;
+#[cfg(feature = "std")]
+pub use crate::training::{train_mlp_from_csv, train_streaming, EpochCallback, FitOptions, Loss};
+
+#[cfg(feature = "std")]
+pub mod adversarial;
+
+#[cfg(feature = "std")]
+pub mod debug;
+
+#[cfg(feature = "std")]
+pub mod ensemble;
+
+#[cfg(feature = "std")]
+pub mod gradcheck;
+
+#[cfg(feature = "std")]
+pub mod init;
+
+#[cfg(feature = "std")]
+pub mod losses;
+
+#[cfg(feature = "std")]
+pub mod metrics;
+
+#[cfg(feature = "profile")]
+pub mod ops;
+
+#[cfg(feature = "std")]
+pub mod parse;
+
+/// The commonly used types, optimizers, losses and utilities in one import:
+///
+/// ```
+/// use rustygrad::prelude::*;
+///
+/// let model = MlpBuilder::new(2).relu(4).linear(1).build();
+/// let mut optimizer = SGD::new(model.parameters(), 0.1);
+///
+/// let out = &model.forward(vec![Value::from(0.5), Value::from(-1.0)])[0];
+/// let loss = losses::sum_of_squares(&[out.clone()]);
+/// model.zero_grad();
+/// loss.backward();
+/// optimizer.step();
+/// ```
+#[cfg(feature = "std")]
+pub mod prelude;
+
+#[cfg(feature = "std")]
+pub mod tuning;
+
+#[cfg(feature = "std")]
+pub mod vecops;
+
+#[cfg(feature = "std")]
+pub mod viz;
@@ -0,0 +1,90 @@
+use crate::Value;
+
+/// `Σ p²` as a single connected graph, e.g. for an L2 penalty. Each parameter
+/// receives a gradient of `2p`.
+pub fn sum_of_squares(params: &[Value]) -> Value {
+    params.iter().map(|p| p * p).sum()
+}
+
+/// Squared error averaged over both the batch and the output dimension.
+pub fn mse_multi(preds: &[Vec<Value>], targets: &[Vec<f64>]) -> Result<Value, String> {
+    if preds.len() != targets.len() {
+        return Err(format!(
+            "batch size mismatch: {} predictions vs {} targets",
+            preds.len(),
+            targets.len()
+        ));
+    }
+    if preds.is_empty() {
+        return Err(String::from("cannot compute the loss of an empty batch"));
+    }
+
+    let mut terms = vec![];
+    for (i, (pred, target)) in preds.iter().zip(targets).enumerate() {
+        if pred.len() != target.len() {
+            return Err(format!(
+                "sample {}: {} outputs vs {} targets",
+                i,
+                pred.len(),
+                target.len()
+            ));
+        }
+        terms.extend(
+            pred.iter()
+                .zip(target)
+                .map(|(p, t)| (p - Value::from(*t)).pow(2.0)),
+        );
+    }
+
+    let n = terms.len() as f64;
+    if n == 0.0 {
+        return Err(String::from("cannot compute the loss of empty outputs"));
+    }
+    Ok(terms.into_iter().sum::<Value>() / n)
+}
+
+/// Softmax cross-entropy of one sample's logits against the index of its class.
+pub fn cross_entropy(logits: &[Value], target: usize) -> Value {
+    assert!(target < logits.len(), "target class out of range");
+
+    // Shifting by the max logit keeps exp from overflowing without changing the result
+    let max = logits
+        .iter()
+        .map(|z| z.borrow().data)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let log_sum_exp = logits.iter().map(|z| (z + -max).exp()).sum::<Value>().log();
+    log_sum_exp - (&logits[target] + -max)
+}
+
+/// Mean `cross_entropy` over a batch, as a single scalar for one backward pass.
+pub fn cross_entropy_batch(logits: &[Vec<Value>], targets: &[usize]) -> Value {
+    assert_eq!(
+        logits.len(),
+        targets.len(),
+        "logits and targets must have the same length"
+    );
+    let n = targets.len() as f64;
+    logits
+        .iter()
+        .zip(targets)
+        .map(|(z, &t)| cross_entropy(z, t))
+        .sum::<Value>()
+        / n
+}
+
+/// Triplet margin loss `relu(d(a, p) - d(a, n) + margin)` with Euclidean `d`:
+/// zero once the negative is at least `margin` further from the anchor than
+/// the positive.
+pub fn triplet(anchor: &[Value], positive: &[Value], negative: &[Value], margin: f64) -> Value {
+    assert!(
+        anchor.len() == positive.len() && anchor.len() == negative.len(),
+        "anchor, positive and negative must have the same length"
+    );
+    (distance(anchor, positive) - distance(anchor, negative) + margin).relu()
+}
+
+// The epsilon keeps the gradient of the square root finite for coincident points
+fn distance(a: &[Value], b: &[Value]) -> Value {
+    let diffs: Vec<Value> = a.iter().zip(b).map(|(x, y)| x - y).collect();
+    (sum_of_squares(&diffs) + 1e-12).pow(0.5)
+}
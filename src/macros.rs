@@ -0,0 +1,140 @@
+// Operator overloading for `Value`, in the call syntax of the `impl_ops` crate
+// that this replaces (that crate needs `std`, which embedded builds lack).
+// Each body is written once for borrowed operands; the owned/borrowed
+// combinations delegate to it.
+
+macro_rules! impl_op_ex {
+    (-|$a:ident: &Value| -> Value $body:block) => {
+        impl core::ops::Neg for &Value {
+            type Output = Value;
+            fn neg(self) -> Value {
+                let $a = self;
+                $body
+            }
+        }
+
+        impl core::ops::Neg for Value {
+            type Output = Value;
+            fn neg(self) -> Value {
+                -&self
+            }
+        }
+    };
+    (+= $($rest:tt)+) => { impl_op_ex!(@assign AddAssign add_assign $($rest)+); };
+    (*= $($rest:tt)+) => { impl_op_ex!(@assign MulAssign mul_assign $($rest)+); };
+    (+ $($rest:tt)+) => { impl_op_ex!(@binary Add add $($rest)+); };
+    (- $($rest:tt)+) => { impl_op_ex!(@binary Sub sub $($rest)+); };
+    (* $($rest:tt)+) => { impl_op_ex!(@binary Mul mul $($rest)+); };
+    (/ $($rest:tt)+) => { impl_op_ex!(@binary Div div $($rest)+); };
+
+    (@binary $trait:ident $method:ident |$a:ident: &Value, $b:ident: &Value| -> Value $body:block) => {
+        impl core::ops::$trait<&Value> for &Value {
+            type Output = Value;
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn $method(self, $b: &Value) -> Value {
+                let $a = self;
+                $body
+            }
+        }
+
+        impl core::ops::$trait<Value> for &Value {
+            type Output = Value;
+            fn $method(self, rhs: Value) -> Value {
+                core::ops::$trait::$method(self, &rhs)
+            }
+        }
+
+        impl core::ops::$trait<&Value> for Value {
+            type Output = Value;
+            fn $method(self, rhs: &Value) -> Value {
+                core::ops::$trait::$method(&self, rhs)
+            }
+        }
+
+        impl core::ops::$trait<Value> for Value {
+            type Output = Value;
+            fn $method(self, rhs: Value) -> Value {
+                core::ops::$trait::$method(&self, &rhs)
+            }
+        }
+    };
+    (@binary $trait:ident $method:ident |$a:ident: &Value, $b:ident: f64| -> Value $body:block) => {
+        impl core::ops::$trait<f64> for &Value {
+            type Output = Value;
+            fn $method(self, $b: f64) -> Value {
+                let $a = self;
+                $body
+            }
+        }
+
+        impl core::ops::$trait<f64> for Value {
+            type Output = Value;
+            fn $method(self, rhs: f64) -> Value {
+                core::ops::$trait::$method(&self, rhs)
+            }
+        }
+    };
+    (@binary $trait:ident $method:ident |$a:ident: f64, $b:ident: &Value| -> Value $body:block) => {
+        impl core::ops::$trait<&Value> for f64 {
+            type Output = Value;
+            fn $method(self, $b: &Value) -> Value {
+                let $a = self;
+                $body
+            }
+        }
+
+        impl core::ops::$trait<Value> for f64 {
+            type Output = Value;
+            fn $method(self, rhs: Value) -> Value {
+                core::ops::$trait::$method(self, &rhs)
+            }
+        }
+    };
+
+    (@assign $trait:ident $method:ident |$a:ident: &mut Value, $b:ident: &Value| $body:block) => {
+        impl core::ops::$trait<&Value> for Value {
+            fn $method(&mut self, $b: &Value) {
+                let $a = self;
+                $body
+            }
+        }
+
+        impl core::ops::$trait<Value> for Value {
+            fn $method(&mut self, rhs: Value) {
+                core::ops::$trait::$method(self, &rhs)
+            }
+        }
+    };
+    (@assign $trait:ident $method:ident |$a:ident: &mut Value, $b:ident: f64| $body:block) => {
+        impl core::ops::$trait<f64> for Value {
+            fn $method(&mut self, $b: f64) {
+                let $a = self;
+                $body
+            }
+        }
+    };
+}
+
+// `impl_op_ex!` for `&Value op f64`, plus the mirrored `f64 op &Value`.
+macro_rules! impl_op_ex_commutative {
+    (+ $($rest:tt)+) => { impl_op_ex_commutative!(@mirror Add add + $($rest)+); };
+    (* $($rest:tt)+) => { impl_op_ex_commutative!(@mirror Mul mul * $($rest)+); };
+
+    (@mirror $trait:ident $method:ident $op:tt |$a:ident: &Value, $b:ident: f64| -> Value $body:block) => {
+        impl_op_ex!($op |$a: &Value, $b: f64| -> Value $body);
+
+        impl core::ops::$trait<&Value> for f64 {
+            type Output = Value;
+            fn $method(self, rhs: &Value) -> Value {
+                core::ops::$trait::$method(rhs, self)
+            }
+        }
+
+        impl core::ops::$trait<Value> for f64 {
+            type Output = Value;
+            fn $method(self, rhs: Value) -> Value {
+                core::ops::$trait::$method(&rhs, self)
+            }
+        }
+    };
+}
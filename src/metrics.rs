@@ -0,0 +1,33 @@
+use crate::Value;
+
+/// Mean of the per-class recalls, treating `score > 0` and `target > 0` as the
+/// positive class (as with `{-1, 1}` labels).
+///
+/// Unlike plain accuracy, a classifier that always predicts the majority class
+/// scores `0.5` however imbalanced the data is. Classes absent from `targets`
+/// are left out of the mean.
+pub fn balanced_accuracy(scores: &[Value], targets: &[f64]) -> f64 {
+    assert_eq!(
+        scores.len(),
+        targets.len(),
+        "scores and targets must have the same length"
+    );
+
+    let mut hits = [0usize; 2];
+    let mut totals = [0usize; 2];
+    for (score, target) in scores.iter().zip(targets) {
+        let class = (*target > 0.0) as usize;
+        totals[class] += 1;
+        if (score.borrow().data > 0.0) == (*target > 0.0) {
+            hits[class] += 1;
+        }
+    }
+
+    let recalls: Vec<f64> = hits
+        .iter()
+        .zip(&totals)
+        .filter(|(_, &total)| total > 0)
+        .map(|(&hit, &total)| hit as f64 / total as f64)
+        .collect();
+    recalls.iter().sum::<f64>() / recalls.len().max(1) as f64
+}
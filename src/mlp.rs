@@ -1,33 +1,402 @@
-use crate::{Layer, Value};
+use crate::{Activation, Layer, Neuron, Value};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt::{self, Debug};
+use std::ops::Index;
+#[cfg(feature = "profile")]
+use std::{cell::Cell, time::Duration, time::Instant};
 
-#[derive(Debug)]
 pub struct MLP {
+    pub(crate) layers: Vec<Layer>,
+    // Flattened view of the layer parameters, built once. The `Rc`s alias the
+    // layers' own values, so updates through either side are shared.
+    params: Vec<Value>,
+    #[cfg(feature = "profile")]
+    profile: Cell<Profile>,
+}
+
+impl Debug for MLP {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MLP").field("layers", &self.layers).finish()
+    }
+}
+
+/// Timings and allocations of the most recent `MLP::forward` and `MLP::backward`.
+#[cfg(feature = "profile")]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Profile {
+    pub forward_time: Duration,
+    pub backward_time: Duration,
+    /// `Value` nodes allocated by the forward pass
+    pub nodes: usize,
+}
+
+pub struct MlpBuilder {
+    nin: i32,
     layers: Vec<Layer>,
+    rng: Option<StdRng>,
+}
+
+impl MlpBuilder {
+    pub fn new(nin: i32) -> MlpBuilder {
+        MlpBuilder {
+            nin,
+            layers: vec![],
+            rng: None,
+        }
+    }
+
+    /// Like `new`, but every layer is initialized from one generator seeded with `seed`.
+    pub fn seeded(nin: i32, seed: u64) -> MlpBuilder {
+        MlpBuilder {
+            rng: Some(StdRng::seed_from_u64(seed)),
+            ..MlpBuilder::new(nin)
+        }
+    }
+
+    pub fn relu(self, nout: i32) -> MlpBuilder {
+        self.layer(nout, Activation::ReLU)
+    }
+
+    pub fn linear(self, nout: i32) -> MlpBuilder {
+        self.layer(nout, Activation::Linear)
+    }
+
+    pub fn build(self) -> MLP {
+        MLP::from_layers(self.layers)
+    }
+
+    pub fn layer(mut self, nout: i32, activation: Activation) -> MlpBuilder {
+        let nin = self.width();
+        let layer = match self.rng.as_mut() {
+            Some(rng) => Layer::with_rng(nin, nout, activation, rng),
+            None => Layer::with_activation(nin, nout, activation),
+        };
+        self.layers.push(layer);
+        self
+    }
+
+    // Output size of the last layer added so far
+    fn width(&self) -> i32 {
+        self.layers.last().map_or(self.nin, |l| l.nout())
+    }
 }
 
 impl MLP {
-    pub fn new(nin: i32, mut nouts: Vec<i32>) -> MLP {
+    pub fn new(nin: i32, nouts: Vec<i32>) -> MLP {
+        MLP::with_rng(nin, nouts, &mut rand::thread_rng())
+    }
+
+    /// `MLP::new` with reproducible initialization.
+    pub fn seeded(nin: i32, nouts: Vec<i32>, seed: u64) -> MLP {
+        MLP::with_rng(nin, nouts, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// `MLP::new`, drawing every layer's initial weights from `rng` in order.
+    pub fn with_rng<R: Rng>(nin: i32, mut nouts: Vec<i32>, rng: &mut R) -> MLP {
         nouts.insert(0, nin);
         let mut layers: Vec<Layer> = vec![];
         let n = nouts.len() - 1;
 
         for i in 0..n {
-            layers.push(Layer::new(nouts[i], nouts[i + 1], i != n - 1));
+            let activation = if i != n - 1 {
+                Activation::ReLU
+            } else {
+                Activation::Linear
+            };
+            layers.push(Layer::with_rng(nouts[i], nouts[i + 1], activation, rng));
+        }
+        MLP::from_layers(layers)
+    }
+
+    /// Parses a hyphen-separated architecture such as `"2-16-16-1"` into
+    /// `MLP::new(2, vec![16, 16, 1])`.
+    pub fn from_arch_str(s: &str) -> Result<MLP, String> {
+        let sizes = s
+            .split('-')
+            .map(|part| match part.trim().parse::<i32>() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(format!(
+                    "invalid layer size {:?} in architecture {:?}",
+                    part, s
+                )),
+            })
+            .collect::<Result<Vec<i32>, String>>()?;
+        if sizes.len() < 2 {
+            return Err(format!(
+                "architecture {:?} needs an input size and at least one layer",
+                s
+            ));
+        }
+        Ok(MLP::new(sizes[0], sizes[1..].to_vec()))
+    }
+
+    pub(crate) fn from_layers(layers: Vec<Layer>) -> MLP {
+        let params = layers.iter().flat_map(|l| l.parameters()).collect();
+        MLP {
+            layers,
+            params,
+            #[cfg(feature = "profile")]
+            profile: Cell::new(Profile::default()),
         }
-        MLP { layers }
     }
 
     pub fn forward(&self, x: Vec<Value>) -> Vec<Value> {
-        self.layers.iter().fold(x, |x, layer| layer.forward(&x))
+        #[cfg(feature = "profile")]
+        let (start, nodes) = (Instant::now(), crate::engine::nodes_created());
+
+        let out = self.layers.iter().fold(x, |x, layer| layer.forward(&x));
+
+        #[cfg(feature = "profile")]
+        self.profile.set(Profile {
+            forward_time: start.elapsed(),
+            nodes: crate::engine::nodes_created() - nodes,
+            ..self.profile.get()
+        });
+        out
+    }
+
+    /// Evaluates the model on raw floats without building a graph.
+    pub fn predict(&self, x: &[f64]) -> Vec<f64> {
+        self.layers.iter().fold(x.to_vec(), |x, layer| {
+            layer
+                .neurons
+                .iter()
+                .map(|n| {
+                    let wx: f64 =
+                        n.w.iter()
+                            .zip(&x)
+                            .map(|(wi, xi)| wi.borrow().data * xi)
+                            .sum();
+                    n.activation.apply_f64(wx + n.b.borrow().data)
+                })
+                .collect()
+        })
+    }
+
+    /// `1.0` if the first output is positive, else `-1.0`, matching `{-1, 1}` labels.
+    pub fn predict_class(&self, x: &[f64]) -> f64 {
+        if self.predict(x)[0] > 0.0 {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    /// Runs `loss.backward()`, timing it when profiling is enabled.
+    pub fn backward(&self, loss: &Value) {
+        #[cfg(feature = "profile")]
+        let start = Instant::now();
+
+        loss.backward();
+
+        #[cfg(feature = "profile")]
+        self.profile.set(Profile {
+            backward_time: start.elapsed(),
+            ..self.profile.get()
+        });
+    }
+
+    #[cfg(feature = "profile")]
+    pub fn last_profile(&self) -> Profile {
+        self.profile.get()
+    }
+
+    /// Lifts raw inputs to leaf `Value`s and runs `forward`. The outputs remain
+    /// connected to the parameters, so they can still be backpropagated.
+    pub fn forward_f64(&self, x: &[f64]) -> Vec<Value> {
+        self.forward(x.iter().map(|&xi| Value::from(xi)).collect())
+    }
+
+    /// Gradient of the first output w.r.t. each input, for saliency maps and
+    /// adversarial examples. Parameter grads are left as they were.
+    pub fn input_gradient(&self, x: &[f64]) -> Vec<f64> {
+        let saved: Vec<f64> = self.parameters_iter().map(|p| p.borrow().grad).collect();
+
+        let inputs: Vec<Value> = x.iter().map(|&xi| Value::from(xi)).collect();
+        let out = &self.forward(inputs.clone())[0];
+        out.backward();
+
+        for (p, grad) in self.parameters_iter().zip(saved) {
+            p.borrow_mut().grad = grad;
+        }
+        inputs.iter().map(|xi| xi.borrow().grad).collect()
+    }
+
+    /// Like `forward_f64`, but pads `x` with `pad_value` or truncates it to the
+    /// model's input size. The returned flag is `true` when `x` had the wrong
+    /// length, so callers can warn or reject the input as they see fit.
+    pub fn forward_padded(&self, x: &[f64], pad_value: f64) -> (Vec<Value>, bool) {
+        let nin = self.nin();
+        let padded: Vec<f64> = x
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(pad_value))
+            .take(nin)
+            .collect();
+        (self.forward_f64(&padded), x.len() != nin)
+    }
+
+    /// Number of inputs the first layer expects, `0` for a model without layers.
+    pub fn nin(&self) -> usize {
+        self.layers
+            .first()
+            .and_then(|l| l.neurons.first())
+            .map_or(0, |n| n.w.len())
+    }
+
+    /// Like `forward`, but also returns the output of every layer (the last one is the output).
+    pub fn forward_with_activations(&self, x: Vec<Value>) -> (Vec<Value>, Vec<Vec<Value>>) {
+        let mut activations: Vec<Vec<Value>> = vec![];
+        let out = self.layers.iter().fold(x, |x, layer| {
+            let out = layer.forward(&x);
+            activations.push(out.clone());
+            out
+        });
+        (out, activations)
+    }
+
+    /// The `i`-th layer, or `None` past the output layer. `model[i]` panics instead.
+    pub fn layer(&self, i: usize) -> Option<&Layer> {
+        self.layers.get(i)
+    }
+
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn parameters_iter(&self) -> impl Iterator<Item = &Value> {
+        self.params.iter()
     }
 
     pub fn parameters(&self) -> Vec<Value> {
-        self.layers.iter().flat_map(|l| l.parameters()).collect()
+        self.params.clone()
+    }
+
+    /// Every weight but none of the biases, e.g. for weight decay.
+    pub fn weight_parameters(&self) -> Vec<Value> {
+        self.layers
+            .iter()
+            .flat_map(|l| &l.neurons)
+            .flat_map(|n| n.w.iter().cloned())
+            .collect()
+    }
+
+    /// Evaluates the model over a `resolution × resolution` grid spanning
+    /// `[-bound, bound)` on both axes. Rows run from top (`y = bound`) to bottom.
+    pub fn decision_grid(&self, bound: f64, resolution: usize) -> Vec<Vec<f64>> {
+        let step = 2.0 * bound / resolution as f64;
+        (0..resolution)
+            .map(|i| {
+                let y = bound - i as f64 * step;
+                (0..resolution)
+                    .map(|j| {
+                        let x = -bound + j as f64 * step;
+                        let out = &self.forward_f64(&[x, y])[0];
+                        let data = out.borrow().data;
+                        data
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Counts parameter values into `bins` equal-width buckets spanning their range.
+    pub fn weight_histogram(&self, bins: usize) -> Vec<usize> {
+        let data: Vec<f64> = self.parameters_iter().map(|p| p.borrow().data).collect();
+        histogram(&data, bins)
+    }
+
+    /// Counts parameter gradients into `bins` equal-width buckets spanning their range.
+    pub fn grad_histogram(&self, bins: usize) -> Vec<usize> {
+        let grads: Vec<f64> = self.parameters_iter().map(|p| p.borrow().grad).collect();
+        histogram(&grads, bins)
     }
 
     pub fn zero_grad(&self) {
-        for p in self.parameters() {
+        for p in self.parameters_iter() {
             p.borrow_mut().grad = 0.0;
         }
     }
+
+    /// Builds a new model whose parameters are the element-wise mean of
+    /// `models` ("model soup"). All models must share the same architecture.
+    pub fn average(models: &[&MLP]) -> Result<MLP, String> {
+        let first = models.first().ok_or("expected at least one model")?;
+        for (i, model) in models.iter().enumerate().skip(1) {
+            let same_shape = model.layers.len() == first.layers.len()
+                && model.layers.iter().zip(&first.layers).all(|(a, b)| {
+                    a.neurons.len() == b.neurons.len()
+                        && a.neurons
+                            .iter()
+                            .zip(&b.neurons)
+                            .all(|(m, n)| m.w.len() == n.w.len() && m.activation == n.activation)
+                });
+            if !same_shape {
+                return Err(format!("model {} has a different architecture", i));
+            }
+        }
+
+        let mut sums: Vec<f64> = vec![0.0; first.params.len()];
+        for model in models {
+            for (sum, p) in sums.iter_mut().zip(model.parameters_iter()) {
+                *sum += p.borrow().data;
+            }
+        }
+
+        let layers = first
+            .layers
+            .iter()
+            .map(|layer| {
+                let neurons = layer
+                    .neurons
+                    .iter()
+                    .map(|n| Neuron::with_weights(vec![0.0; n.w.len()], 0.0, n.activation))
+                    .collect();
+                Layer::from_neurons(neurons)
+            })
+            .collect();
+        let soup = MLP::from_layers(layers);
+        for (p, sum) in soup.parameters_iter().zip(sums) {
+            p.borrow_mut().data = sum / models.len() as f64;
+        }
+        Ok(soup)
+    }
+}
+
+fn histogram(values: &[f64], bins: usize) -> Vec<usize> {
+    let mut counts = vec![0; bins];
+    if bins == 0 {
+        return counts;
+    }
+
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let width = (max - min) / bins as f64;
+
+    for v in values {
+        // The maximum lands on the upper edge, so fold it into the last bin
+        let bin = if width > 0.0 {
+            (((v - min) / width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        counts[bin] += 1;
+    }
+    counts
+}
+
+impl Index<usize> for MLP {
+    type Output = Layer;
+
+    fn index(&self, i: usize) -> &Layer {
+        &self.layers[i]
+    }
+}
+
+impl TryFrom<&str> for MLP {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<MLP, String> {
+        MLP::from_arch_str(s)
+    }
 }
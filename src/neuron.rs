@@ -1,29 +1,51 @@
-use crate::Value;
+use crate::engine::pairwise_sum;
+use crate::{Activation, Value};
 use rand::{distributions::Uniform, Rng};
 use std::fmt::{self, Debug};
 
 pub struct Neuron {
-    w: Vec<Value>,
-    b: Value,
-    nonlin: bool,
+    pub(crate) w: Vec<Value>,
+    pub(crate) b: Value,
+    pub(crate) activation: Activation,
 }
 
 impl Debug for Neuron {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let name = if self.nonlin { "ReLU" } else { "Linear" };
-        write!(f, "{}({})", name, self.w.len())
+        write!(f, "{}({})", self.activation, self.w.len())
     }
 }
 
 impl Neuron {
     pub fn new(nin: i32, nonlin: bool) -> Neuron {
-        let mut rng = rand::thread_rng();
+        let activation = if nonlin {
+            Activation::ReLU
+        } else {
+            Activation::Linear
+        };
+        Neuron::with_activation(nin, activation)
+    }
+
+    pub fn with_activation(nin: i32, activation: Activation) -> Neuron {
+        Neuron::with_rng(nin, activation, &mut rand::thread_rng())
+    }
+
+    /// Draws the initial weights from `rng`, so a seeded generator makes
+    /// construction reproducible.
+    pub fn with_rng<R: Rng>(nin: i32, activation: Activation, rng: &mut R) -> Neuron {
         let range = Uniform::<f64>::new(-1.0, 1.0);
 
         Neuron {
             w: (0..nin).map(|_| Value::from(rng.sample(range))).collect(),
             b: Value::from(0.0),
-            nonlin,
+            activation,
+        }
+    }
+
+    pub(crate) fn with_weights(w: Vec<f64>, b: f64, activation: Activation) -> Neuron {
+        Neuron {
+            w: w.into_iter().map(Value::from).collect(),
+            b: Value::from(b),
+            activation,
         }
     }
 
@@ -31,14 +53,23 @@ impl Neuron {
         Neuron::new(nin, true)
     }
 
-    pub fn forward(&self, x: &Vec<Value>) -> Value {
-        let wixi_sum: Value = self.w.iter().zip(x).map(|(wi, xi)| wi * xi).sum();
-        let out = wixi_sum + &self.b;
+    pub fn forward(&self, x: &[Value]) -> Value {
+        self.forward_with_preact(x).1
+    }
 
-        if self.nonlin {
-            return out.relu();
-        }
-        out
+    /// Returns `(wx + b, activation(wx + b))`. For linear neurons both are the same node.
+    pub fn forward_with_preact(&self, x: &[Value]) -> (Value, Value) {
+        let wixi: Vec<Value> = self.w.iter().zip(x).map(|(wi, xi)| wi * xi).collect();
+        // Pairwise, so wide layers do not accumulate a long chain of rounding error
+        let preact = pairwise_sum(&wixi) + &self.b;
+
+        let out = self.activation.apply(&preact);
+        (preact, out)
+    }
+
+    /// Borrows the parameters in the same order as `parameters`, without cloning.
+    pub fn parameters_iter(&self) -> impl Iterator<Item = &Value> {
+        std::iter::once(&self.b).chain(self.w.iter())
     }
 
     pub fn parameters(&self) -> Vec<Value> {
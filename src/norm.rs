@@ -0,0 +1,93 @@
+use crate::{losses, Activation, Neuron, Value};
+
+/// Normalizes a single sample across its features, then applies a learnable
+/// per-feature scale `gamma` and shift `beta`.
+#[derive(Debug)]
+pub struct LayerNorm {
+    pub gamma: Vec<Value>,
+    pub beta: Vec<Value>,
+    pub eps: f64,
+}
+
+impl LayerNorm {
+    pub fn new(nfeatures: usize, eps: f64) -> LayerNorm {
+        LayerNorm {
+            gamma: (0..nfeatures).map(|_| Value::from(1.0)).collect(),
+            beta: (0..nfeatures).map(|_| Value::from(0.0)).collect(),
+            eps,
+        }
+    }
+
+    pub fn forward(&self, x: &[Value]) -> Vec<Value> {
+        assert_eq!(x.len(), self.gamma.len(), "feature count mismatch");
+        let n = x.len() as f64;
+
+        let mean = x.iter().sum::<Value>() / n;
+        let centered: Vec<Value> = x.iter().map(|xi| xi - &mean).collect();
+        let var = centered.iter().map(|c| c * c).sum::<Value>() / n;
+        let inv_std = (var + self.eps).pow(-0.5);
+
+        centered
+            .iter()
+            .zip(self.gamma.iter().zip(&self.beta))
+            .map(|(c, (g, b))| c * &inv_std * g + b)
+            .collect()
+    }
+
+    pub fn parameters(&self) -> Vec<Value> {
+        self.gamma.iter().chain(&self.beta).cloned().collect()
+    }
+}
+
+/// Weight normalization of a neuron: the weights are reparameterized as
+/// `g * v / ||v||`, decoupling their length `g` from their direction `v`.
+#[derive(Debug)]
+pub struct WeightNorm {
+    pub g: Value,
+    pub v: Vec<Value>,
+    pub b: Value,
+    pub activation: Activation,
+}
+
+impl WeightNorm {
+    /// Wraps `neuron`, starting from `g = ||w||` and `v = w` so the effective
+    /// weights are unchanged.
+    pub fn new(neuron: Neuron) -> WeightNorm {
+        let norm = neuron
+            .w
+            .iter()
+            .map(|w| w.borrow().data.powi(2))
+            .sum::<f64>()
+            .sqrt();
+        WeightNorm {
+            g: Value::from(norm),
+            v: neuron.w,
+            b: neuron.b,
+            activation: neuron.activation,
+        }
+    }
+
+    /// The effective weights `g * v / ||v||`, connected to both `g` and `v`.
+    pub fn weights(&self) -> Vec<Value> {
+        let scale = &self.g / losses::sum_of_squares(&self.v).pow(0.5);
+        self.v.iter().map(|vi| vi * &scale).collect()
+    }
+
+    pub fn forward(&self, x: &[Value]) -> Value {
+        let preact = self
+            .weights()
+            .iter()
+            .zip(x)
+            .map(|(wi, xi)| wi * xi)
+            .sum::<Value>()
+            + &self.b;
+        self.activation.apply(&preact)
+    }
+
+    /// `[b, g, v...]`
+    pub fn parameters(&self) -> Vec<Value> {
+        let mut out = vec![self.b.clone(), self.g.clone()];
+        out.extend(self.v.iter().cloned());
+        out
+    }
+}
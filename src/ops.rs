@@ -0,0 +1,20 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    static OP_COUNTS: RefCell<HashMap<String, usize>> = RefCell::new(HashMap::new());
+}
+
+pub(crate) fn record(op: &str) {
+    OP_COUNTS.with(|counts| *counts.borrow_mut().entry(op.to_string()).or_insert(0) += 1);
+}
+
+/// Number of nodes created per op (keyed by `_op`) on this thread since the
+/// last `reset`, for estimating the cost of a forward pass.
+pub fn op_counts() -> HashMap<String, usize> {
+    OP_COUNTS.with(|counts| counts.borrow().clone())
+}
+
+pub fn reset() {
+    OP_COUNTS.with(|counts| counts.borrow_mut().clear());
+}
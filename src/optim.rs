@@ -0,0 +1,366 @@
+use crate::json::{self, Json};
+use crate::utils::sample_normal;
+use crate::{Value, MLP};
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+pub trait Optimizer {
+    /// Updates every parameter that `requires_grad` from its current `grad`.
+    fn step(&mut self);
+}
+
+pub struct SGD {
+    groups: Vec<(Vec<Value>, f64)>,
+    pub momentum: f64,
+    velocity: HashMap<u64, f64>,
+}
+
+impl SGD {
+    pub fn new(params: Vec<Value>, lr: f64) -> SGD {
+        SGD::with_groups(vec![(params, lr)])
+    }
+
+    /// Each group of parameters is stepped with its own learning rate.
+    pub fn with_groups(groups: Vec<(Vec<Value>, f64)>) -> SGD {
+        SGD {
+            groups,
+            momentum: 0.0,
+            velocity: HashMap::new(),
+        }
+    }
+
+    /// Heavy-ball momentum: `v = momentum * v + grad`, then `data -= lr * v`.
+    pub fn with_momentum(params: Vec<Value>, lr: f64, momentum: f64) -> SGD {
+        SGD {
+            momentum,
+            ..SGD::new(params, lr)
+        }
+    }
+
+    pub fn save_state(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let params: Vec<&Value> = self.groups.iter().flat_map(|(ps, _)| ps).collect();
+        save_buffers(path, 0, &params, &[("velocity", &self.velocity)])
+    }
+
+    pub fn load_state(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let params: Vec<&Value> = self.groups.iter().flat_map(|(ps, _)| ps).collect();
+        load_buffers(path, &params, &mut [("velocity", &mut self.velocity)])?;
+        Ok(())
+    }
+
+    /// Overrides the learning rate of every group.
+    pub fn set_lr(&mut self, lr: f64) {
+        for group in self.groups.iter_mut() {
+            group.1 = lr;
+        }
+    }
+}
+
+impl Optimizer for SGD {
+    fn step(&mut self) {
+        for (params, lr) in self.groups.iter() {
+            for p in params {
+                let mut p = p.borrow_mut();
+                if !p.requires_grad {
+                    continue;
+                }
+                if self.momentum == 0.0 {
+                    p.data -= lr * p.grad;
+                } else {
+                    let v = self.velocity.entry(p.id).or_insert(0.0);
+                    *v = self.momentum * *v + p.grad;
+                    p.data -= lr * *v;
+                }
+            }
+        }
+    }
+}
+
+/// Scales each parameter's step by the inverse root of its accumulated squared gradients.
+pub struct Adagrad {
+    params: Vec<Value>,
+    pub lr: f64,
+    pub eps: f64,
+    sum_sq: HashMap<u64, f64>,
+}
+
+impl Adagrad {
+    pub fn new(params: Vec<Value>, lr: f64, eps: f64) -> Adagrad {
+        Adagrad {
+            params,
+            lr,
+            eps,
+            sum_sq: HashMap::new(),
+        }
+    }
+
+    pub fn save_state(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let params: Vec<&Value> = self.params.iter().collect();
+        save_buffers(path, 0, &params, &[("sum_sq", &self.sum_sq)])
+    }
+
+    pub fn load_state(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let params: Vec<&Value> = self.params.iter().collect();
+        load_buffers(path, &params, &mut [("sum_sq", &mut self.sum_sq)])?;
+        Ok(())
+    }
+}
+
+impl Optimizer for Adagrad {
+    fn step(&mut self) {
+        for p in self.params.iter() {
+            let mut p = p.borrow_mut();
+            if !p.requires_grad {
+                continue;
+            }
+            let sum_sq = self.sum_sq.entry(p.id).or_insert(0.0);
+            *sum_sq += p.grad * p.grad;
+            p.data -= self.lr * p.grad / (sum_sq.sqrt() + self.eps);
+        }
+    }
+}
+
+/// Divides each step by a running root-mean-square of recent gradients.
+pub struct RMSProp {
+    params: Vec<Value>,
+    pub lr: f64,
+    pub decay: f64,
+    pub eps: f64,
+    mean_sq: HashMap<u64, f64>,
+}
+
+impl RMSProp {
+    pub fn new(params: Vec<Value>, lr: f64) -> RMSProp {
+        RMSProp {
+            params,
+            lr,
+            decay: 0.99,
+            eps: 1e-8,
+            mean_sq: HashMap::new(),
+        }
+    }
+
+    pub fn save_state(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let params: Vec<&Value> = self.params.iter().collect();
+        save_buffers(path, 0, &params, &[("mean_sq", &self.mean_sq)])
+    }
+
+    pub fn load_state(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let params: Vec<&Value> = self.params.iter().collect();
+        load_buffers(path, &params, &mut [("mean_sq", &mut self.mean_sq)])?;
+        Ok(())
+    }
+}
+
+impl Optimizer for RMSProp {
+    fn step(&mut self) {
+        for p in self.params.iter() {
+            let mut p = p.borrow_mut();
+            if !p.requires_grad {
+                continue;
+            }
+            let s = self.mean_sq.entry(p.id).or_insert(0.0);
+            *s = self.decay * *s + (1.0 - self.decay) * p.grad * p.grad;
+            p.data -= self.lr * p.grad / (s.sqrt() + self.eps);
+        }
+    }
+}
+
+/// Adam with bias-corrected first and second moment estimates.
+pub struct Adam {
+    params: Vec<Value>,
+    pub lr: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub eps: f64,
+    t: usize,
+    m: HashMap<u64, f64>,
+    v: HashMap<u64, f64>,
+}
+
+impl Adam {
+    pub fn new(params: Vec<Value>, lr: f64) -> Adam {
+        Adam {
+            params,
+            lr,
+            beta1: 0.9,
+            beta2: 0.999,
+            eps: 1e-8,
+            t: 0,
+            m: HashMap::new(),
+            v: HashMap::new(),
+        }
+    }
+
+    /// Writes the step count and moment buffers as JSON, in parameter order.
+    pub fn save_state(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let params: Vec<&Value> = self.params.iter().collect();
+        save_buffers(path, self.t, &params, &[("m", &self.m), ("v", &self.v)])
+    }
+
+    /// Restores a `save_state` file. Buffers are matched to this optimizer's
+    /// parameters by position, since node ids differ between runs.
+    pub fn load_state(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let params: Vec<&Value> = self.params.iter().collect();
+        self.t = load_buffers(path, &params, &mut [("m", &mut self.m), ("v", &mut self.v)])?;
+        Ok(())
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&mut self) {
+        self.t += 1;
+        let correction1 = 1.0 - self.beta1.powi(self.t as i32);
+        let correction2 = 1.0 - self.beta2.powi(self.t as i32);
+
+        for p in self.params.iter() {
+            let mut p = p.borrow_mut();
+            if !p.requires_grad {
+                continue;
+            }
+            let m = self.m.entry(p.id).or_insert(0.0);
+            *m = self.beta1 * *m + (1.0 - self.beta1) * p.grad;
+            let v = self.v.entry(p.id).or_insert(0.0);
+            *v = self.beta2 * *v + (1.0 - self.beta2) * p.grad * p.grad;
+
+            let m_hat = *m / correction1;
+            let v_hat = *v / correction2;
+            p.data -= self.lr * m_hat / (v_hat.sqrt() + self.eps);
+        }
+    }
+}
+
+// Saves `{"step": t, "<name>": [..], ...}` with one entry per parameter, in order
+fn save_buffers(
+    path: &str,
+    step: usize,
+    params: &[&Value],
+    buffers: &[(&str, &HashMap<u64, f64>)],
+) -> Result<(), Box<dyn Error>> {
+    let mut fields = vec![format!("\"step\": {}", step)];
+    for (name, buffer) in buffers {
+        let values: Vec<String> = params
+            .iter()
+            .map(|p| format!("{}", buffer.get(&p.borrow().id).unwrap_or(&0.0)))
+            .collect();
+        fields.push(format!("\"{}\": [{}]", name, values.join(", ")));
+    }
+    fs::write(path, format!("{{{}}}\n", fields.join(", ")))?;
+    Ok(())
+}
+
+// Inverse of `save_buffers`, re-keying entries by the ids of `params`. Returns the step.
+fn load_buffers(
+    path: &str,
+    params: &[&Value],
+    buffers: &mut [(&str, &mut HashMap<u64, f64>)],
+) -> Result<usize, Box<dyn Error>> {
+    let root = json::parse(&fs::read_to_string(path)?)?;
+    let step = root
+        .get("step")
+        .and_then(Json::as_f64)
+        .ok_or("missing \"step\"")?;
+
+    // Parse every buffer before touching any, so a bad file leaves the optimizer as it was
+    let mut loaded = Vec::with_capacity(buffers.len());
+    for (name, _) in buffers.iter() {
+        let values = root
+            .get(name)
+            .and_then(Json::as_array)
+            .ok_or(format!("missing \"{}\" buffer", name))?;
+        if values.len() != params.len() {
+            return Err(format!(
+                "\"{}\" has {} entries but the optimizer has {} parameters",
+                name,
+                values.len(),
+                params.len()
+            )
+            .into());
+        }
+        let mut parsed = HashMap::with_capacity(params.len());
+        for (p, value) in params.iter().zip(values) {
+            let value = value
+                .as_f64()
+                .ok_or(format!("\"{}\" must contain numbers", name))?;
+            parsed.insert(p.borrow().id, value);
+        }
+        loaded.push(parsed);
+    }
+
+    for ((_, buffer), parsed) in buffers.iter_mut().zip(loaded) {
+        **buffer = parsed;
+    }
+
+    Ok(step as usize)
+}
+
+/// Adds `N(0, stddev²)` noise to every parameter's `grad`, typically right before `step`.
+pub fn add_grad_noise(params: &[Value], stddev: f64, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    for p in params {
+        p.borrow_mut().grad += sample_normal(&mut rng, 0.0, stddev);
+    }
+}
+
+/// Polyak averaging: `target.data = (1 - tau) * target.data + tau * source.data`.
+/// Only `data` is touched; neither graph nor `grad` changes.
+pub fn lerp_data_into(target: &[Value], source: &[Value], tau: f64) {
+    assert_eq!(
+        target.len(),
+        source.len(),
+        "target and source must have the same length"
+    );
+    for (t, s) in target.iter().zip(source) {
+        let s = s.borrow().data;
+        let mut t = t.borrow_mut();
+        t.data = (1.0 - tau) * t.data + tau * s;
+    }
+}
+
+/// Stochastic weight averaging: a running mean of a model's parameters over `update` calls.
+#[derive(Debug, Default)]
+pub struct Swa {
+    average: Vec<f64>,
+    count: usize,
+}
+
+impl Swa {
+    pub fn new() -> Swa {
+        Swa::default()
+    }
+
+    pub fn update(&mut self, model: &MLP) {
+        self.count += 1;
+        if self.count == 1 {
+            self.average = model.parameters_iter().map(|p| p.borrow().data).collect();
+            return;
+        }
+
+        assert_eq!(
+            self.average.len(),
+            model.parameters_iter().count(),
+            "architecture changed"
+        );
+        let n = self.count as f64;
+        for (avg, p) in self.average.iter_mut().zip(model.parameters_iter()) {
+            *avg += (p.borrow().data - *avg) / n;
+        }
+    }
+
+    /// Writes the averaged weights into `model`. Does nothing before the first `update`.
+    pub fn finalize(&self, model: &MLP) {
+        if self.count == 0 {
+            return;
+        }
+        assert_eq!(
+            self.average.len(),
+            model.parameters_iter().count(),
+            "architecture changed"
+        );
+        for (avg, p) in self.average.iter().zip(model.parameters_iter()) {
+            p.borrow_mut().data = *avg;
+        }
+    }
+}
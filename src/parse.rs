@@ -0,0 +1,180 @@
+use crate::Value;
+use std::collections::HashMap;
+
+/// Builds the graph of an arithmetic expression such as `"(a+b)*c^2"`.
+///
+/// Supports numbers, the variables in `vars`, `+ - * /`, unary `-`, `^` with a
+/// constant exponent, parentheses and the functions `relu(..)` and `tanh(..)`.
+/// Usual precedence applies, with `^` binding tightest and chaining to the
+/// right (`2^3^2` is `2^9`). Variables are used as-is, so `backward` on the
+/// result fills in their `grad`.
+pub fn parse_expr(s: &str, vars: &HashMap<String, Value>) -> Result<Value, String> {
+    let mut parser = Parser {
+        bytes: s.as_bytes(),
+        pos: 0,
+        vars,
+        vars_used: 0,
+    };
+    let value = parser.expr()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(value),
+        Some(c) => Err(format!("unexpected '{}' at byte {}", c as char, parser.pos)),
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    vars: &'a HashMap<String, Value>,
+    // Variables looked up so far, to tell constant exponents apart
+    vars_used: usize,
+}
+
+impl<'a> Parser<'a> {
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Value, String> {
+        let mut value = self.term()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'+') => {
+                    self.pos += 1;
+                    value += self.term()?;
+                }
+                Some(b'-') => {
+                    self.pos += 1;
+                    value = value - self.term()?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Value, String> {
+        let mut value = self.unary()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'*') => {
+                    self.pos += 1;
+                    value *= self.unary()?;
+                }
+                Some(b'/') => {
+                    self.pos += 1;
+                    value = value / self.unary()?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    // unary := '-' unary | power
+    fn unary(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+            return Ok(-self.unary()?);
+        }
+        self.power()
+    }
+
+    // power := atom ('^' unary)?, so `^` chains to the right. The exponent
+    // may be any expression without variables, since `pow` takes an `f64`.
+    fn power(&mut self) -> Result<Value, String> {
+        let base = self.atom()?;
+        self.skip_whitespace();
+        if self.peek() != Some(b'^') {
+            return Ok(base);
+        }
+        self.pos += 1;
+        let start = self.pos;
+        let vars_used = self.vars_used;
+        let exponent = self.unary()?;
+        if self.vars_used != vars_used {
+            return Err(format!("exponent at byte {} must be constant", start));
+        }
+        let power = exponent.borrow().data;
+        Ok(base.pow(power))
+    }
+
+    // atom := number | name | name '(' expr ')' | '(' expr ')'
+    fn atom(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'(') => {
+                self.pos += 1;
+                let value = self.expr()?;
+                self.expect(b')')?;
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == b'.' => self.number().map(Value::from),
+            Some(c) if c.is_ascii_alphabetic() || c == b'_' => {
+                let start = self.pos;
+                let name = self.name();
+                self.skip_whitespace();
+                if self.peek() == Some(b'(') {
+                    self.pos += 1;
+                    let arg = self.expr()?;
+                    self.expect(b')')?;
+                    return match name {
+                        "relu" => Ok(arg.relu()),
+                        "tanh" => Ok(arg.tanh()),
+                        _ => Err(format!("unknown function {:?} at byte {}", name, start)),
+                    };
+                }
+                self.vars_used += 1;
+                self.vars
+                    .get(name)
+                    .cloned()
+                    .ok_or(format!("unknown variable {:?} at byte {}", name, start))
+            }
+            Some(c) => Err(format!("unexpected '{}' at byte {}", c as char, self.pos)),
+            None => Err(String::from("unexpected end of input")),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == b'.') {
+            self.pos += 1;
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap();
+        text.parse::<f64>()
+            .map_err(|_| format!("invalid number {:?} at byte {}", text, start))
+    }
+
+    fn name(&mut self) -> &'a str {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_')
+        {
+            self.pos += 1;
+        }
+        let bytes = self.bytes;
+        std::str::from_utf8(&bytes[start..self.pos]).unwrap()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(found) if found == c => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(format!("expected '{}' at byte {}", c as char, self.pos)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+}
@@ -0,0 +1,5 @@
+pub use crate::{load_moons_data, read_csv_file, shuffle_in_unison};
+pub use crate::{
+    losses, metrics, vecops, Activation, Adam, FitOptions, Layer, LearningRate, Loss, MlpBuilder,
+    Neuron, Optimizer, StandardScaler, Value, MLP, SGD,
+};
@@ -0,0 +1,119 @@
+pub struct StandardScaler {
+    pub mean: Vec<f64>,
+    pub std: Vec<f64>,
+}
+
+impl StandardScaler {
+    pub fn fit(xs: &[Vec<f64>]) -> StandardScaler {
+        let n = xs.len() as f64;
+        let nfeatures = xs.first().map_or(0, |x| x.len());
+
+        let mut mean = vec![0.0; nfeatures];
+        for x in xs {
+            for (m, xi) in mean.iter_mut().zip(x) {
+                *m += xi / n;
+            }
+        }
+
+        let mut std = vec![0.0; nfeatures];
+        for x in xs {
+            for ((s, m), xi) in std.iter_mut().zip(&mean).zip(x) {
+                *s += (xi - m).powi(2) / n;
+            }
+        }
+        // Constant columns are left unscaled instead of dividing by zero
+        let std = std
+            .into_iter()
+            .map(|v| if v > 0.0 { v.sqrt() } else { 1.0 })
+            .collect();
+
+        StandardScaler { mean, std }
+    }
+
+    pub fn transform(&self, xs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        xs.iter()
+            .map(|x| {
+                x.iter()
+                    .zip(self.mean.iter().zip(&self.std))
+                    .map(|(xi, (m, s))| (xi - m) / s)
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn inverse_transform(&self, xs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        xs.iter()
+            .map(|x| {
+                x.iter()
+                    .zip(self.mean.iter().zip(&self.std))
+                    .map(|(xi, (m, s))| xi * s + m)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Scales each feature linearly onto `feature_range` (`(0, 1)` by default).
+pub struct MinMaxScaler {
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+    pub feature_range: (f64, f64),
+}
+
+impl MinMaxScaler {
+    pub fn fit(xs: &[Vec<f64>]) -> MinMaxScaler {
+        MinMaxScaler::fit_range(xs, (0.0, 1.0))
+    }
+
+    pub fn fit_range(xs: &[Vec<f64>], feature_range: (f64, f64)) -> MinMaxScaler {
+        let nfeatures = xs.first().map_or(0, |x| x.len());
+        let mut min = vec![f64::INFINITY; nfeatures];
+        let mut max = vec![f64::NEG_INFINITY; nfeatures];
+        for x in xs {
+            for (j, xi) in x.iter().enumerate() {
+                min[j] = min[j].min(*xi);
+                max[j] = max[j].max(*xi);
+            }
+        }
+
+        MinMaxScaler {
+            min,
+            max,
+            feature_range,
+        }
+    }
+
+    pub fn transform(&self, xs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let (lo, hi) = self.feature_range;
+        xs.iter()
+            .map(|x| {
+                x.iter()
+                    .enumerate()
+                    .map(|(j, xi)| lo + (xi - self.min[j]) / self.span(j) * (hi - lo))
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn inverse_transform(&self, xs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let (lo, hi) = self.feature_range;
+        xs.iter()
+            .map(|x| {
+                x.iter()
+                    .enumerate()
+                    .map(|(j, xi)| self.min[j] + (xi - lo) / (hi - lo) * self.span(j))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Constant columns get a unit span, so they map onto the lower bound
+    fn span(&self, j: usize) -> f64 {
+        let span = self.max[j] - self.min[j];
+        if span > 0.0 {
+            span
+        } else {
+            1.0
+        }
+    }
+}
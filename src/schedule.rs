@@ -0,0 +1,44 @@
+pub trait Scheduler {
+    /// Learning rate to use at optimization step `step` (0-based).
+    fn lr(&self, step: usize) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LearningRate {
+    Constant(f64),
+    /// Decays linearly from `start` to `end` over `steps`, then stays at `end`
+    Linear {
+        start: f64,
+        end: f64,
+        steps: usize,
+    },
+}
+
+impl Scheduler for LearningRate {
+    fn lr(&self, step: usize) -> f64 {
+        match *self {
+            LearningRate::Constant(lr) => lr,
+            LearningRate::Linear { start, end, steps } => {
+                let t = (step as f64 / steps.max(1) as f64).min(1.0);
+                start + (end - start) * t
+            }
+        }
+    }
+}
+
+/// Ramps linearly from 0 up to the inner schedule's initial rate over
+/// `warmup_steps`, then follows `inner` (restarted at its step 0).
+pub struct Warmup<S: Scheduler> {
+    pub inner: S,
+    pub warmup_steps: usize,
+}
+
+impl<S: Scheduler> Scheduler for Warmup<S> {
+    fn lr(&self, step: usize) -> f64 {
+        if step < self.warmup_steps {
+            self.inner.lr(0) * step as f64 / self.warmup_steps as f64
+        } else {
+            self.inner.lr(step - self.warmup_steps)
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::json::{self, Json};
+use crate::{Activation, Layer, Neuron, MLP};
+use std::error::Error;
+use std::fs;
+
+impl MLP {
+    /// Writes the weights as JSON in the format read by `MLP::load` and
+    /// `load_pytorch_json`, recording each layer's activation so the
+    /// architecture round-trips exactly. Larger than `save`, but human-readable.
+    pub fn save_json(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let layers: Vec<String> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let rows: Vec<String> = layer
+                    .neurons
+                    .iter()
+                    .map(|n| format!("[{}]", join(n.w.iter().map(|w| w.borrow().data))))
+                    .collect();
+                let biases = join(layer.neurons.iter().map(|n| n.b.borrow().data));
+                let activation = layer
+                    .neurons
+                    .first()
+                    .map_or(Activation::Linear, |n| n.activation);
+                format!(
+                    "  {{\"weight\": [{}], \"bias\": [{}], \"activation\": \"{}\"}}",
+                    rows.join(", "),
+                    biases,
+                    activation
+                )
+            })
+            .collect();
+
+        fs::write(path, format!("[\n{}\n]\n", layers.join(",\n")))?;
+        Ok(())
+    }
+
+    /// Loads a file written by `save` or `save_json`, told apart by the
+    /// binary format's magic header.
+    pub fn load(path: &str) -> Result<MLP, Box<dyn Error>> {
+        if fs::read(path)?.starts_with(MAGIC) {
+            return MLP::load_binary(path);
+        }
+        MLP::load_pytorch_json(path)
+    }
+
+    /// Writes a compact, self-describing binary file: the `RGRD` magic, a
+    /// little-endian `u32` format version and layer count, each layer's
+    /// `nin`, `nout` (`u32`) and activation (`u8`), then every parameter as a
+    /// little-endian `f64` in `parameters()` order.
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(FORMAT_VERSION.to_le_bytes());
+        bytes.extend((self.layers.len() as u32).to_le_bytes());
+        for layer in &self.layers {
+            let first = layer.neurons.first();
+            let nin = first.map_or(0, |n| n.w.len());
+            let activation = first.map_or(Activation::Linear, |n| n.activation);
+            bytes.extend((nin as u32).to_le_bytes());
+            bytes.extend((layer.neurons.len() as u32).to_le_bytes());
+            bytes.push(activation_code(activation));
+        }
+        for p in self.parameters_iter() {
+            bytes.extend(p.borrow().data.to_le_bytes());
+        }
+
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Reads a file written by `save`, rejecting foreign files, other
+    /// format versions and truncated or oversized contents.
+    pub fn load_binary(path: &str) -> Result<MLP, Box<dyn Error>> {
+        let bytes = fs::read(path)?;
+        let mut reader = Reader { bytes: &bytes };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err("not a rustygrad binary model (bad magic)".into());
+        }
+        let version = reader.u32()?;
+        if version != FORMAT_VERSION {
+            return Err(format!(
+                "unsupported format version {} (expected {})",
+                version, FORMAT_VERSION
+            )
+            .into());
+        }
+
+        let nlayers = reader.u32()? as usize;
+        // A layer count whose headers cannot fit in the file is rejected
+        // before allocating for it
+        if nlayers > reader.bytes.len() / LAYER_HEADER_LEN {
+            return Err(format!("{} layers do not fit in the file", nlayers).into());
+        }
+        let mut shapes = Vec::with_capacity(nlayers);
+        for i in 0..nlayers {
+            let nin = reader.u32()? as usize;
+            let nout = reader.u32()? as usize;
+            let code = reader.take(1)?[0];
+            let activation = activation_from_code(code)
+                .ok_or(format!("layer {}: unknown activation code {}", i, code))?;
+            if let Some(&(_, prev_nout, _)) = shapes.last() {
+                if prev_nout != nin {
+                    return Err(format!(
+                        "layer {}: expected {} inputs, found {}",
+                        i, prev_nout, nin
+                    )
+                    .into());
+                }
+            }
+            shapes.push((nin, nout, activation));
+        }
+
+        let nbytes = shapes
+            .iter()
+            .try_fold(0usize, |total, (nin, nout, _)| {
+                nin.checked_add(1)?
+                    .checked_mul(*nout)?
+                    .checked_mul(8)?
+                    .checked_add(total)
+            })
+            .ok_or("layer shapes overflow the parameter count")?;
+        if reader.bytes.len() != nbytes {
+            return Err(format!(
+                "expected {} parameter bytes, found {}",
+                nbytes,
+                reader.bytes.len()
+            )
+            .into());
+        }
+
+        let mut layers = vec![];
+        for (nin, nout, activation) in shapes {
+            let mut neurons = vec![];
+            for _ in 0..nout {
+                // `parameters()` order: the bias, then the weights
+                let b = reader.f64()?;
+                let w = (0..nin).map(|_| reader.f64()).collect::<Result<_, _>>()?;
+                neurons.push(Neuron::with_weights(w, b, activation));
+            }
+            layers.push(Layer::from_neurons(neurons));
+        }
+        Ok(MLP::from_layers(layers))
+    }
+
+    /// Loads a JSON list of `{"weight": [[..]], "bias": [..]}` objects, one per
+    /// layer, as exported from a PyTorch `nn.Linear` stack (`weight` is `nout × nin`).
+    ///
+    /// Layer sizes are inferred from the matrices. As in `MLP::new`, every layer
+    /// but the last uses ReLU, unless the object sets `"activation"` (one of
+    /// `"Linear"`, `"ReLU"`, `"Tanh"`, `"Sigmoid"`) or `"nonlin": true/false`.
+    pub fn load_pytorch_json(path: &str) -> Result<MLP, Box<dyn Error>> {
+        let root = json::parse(&fs::read_to_string(path)?)?;
+        let specs = root.as_array().ok_or("expected a JSON array of layers")?;
+        if specs.is_empty() {
+            return Err("expected at least one layer".into());
+        }
+
+        let mut layers = vec![];
+        let mut nin: Option<usize> = None;
+        for (i, spec) in specs.iter().enumerate() {
+            let weight = spec
+                .get("weight")
+                .and_then(Json::as_array)
+                .ok_or(format!("layer {}: missing \"weight\" matrix", i))?;
+            let bias = spec
+                .get("bias")
+                .and_then(Json::as_array)
+                .ok_or(format!("layer {}: missing \"bias\" vector", i))?;
+            if weight.len() != bias.len() {
+                return Err(format!(
+                    "layer {}: {} weight rows but {} biases",
+                    i,
+                    weight.len(),
+                    bias.len()
+                )
+                .into());
+            }
+
+            let activation = match (spec.get("activation"), spec.get("nonlin")) {
+                (Some(Json::String(name)), _) => Activation::from_name(name)
+                    .ok_or(format!("layer {}: unknown activation {:?}", i, name))?,
+                (Some(_), _) => {
+                    return Err(format!("layer {}: \"activation\" must be a string", i).into())
+                }
+                (None, Some(flag)) => match flag.as_bool() {
+                    Some(true) => Activation::ReLU,
+                    Some(false) => Activation::Linear,
+                    None => return Err(format!("layer {}: \"nonlin\" must be a bool", i).into()),
+                },
+                (None, None) if i != specs.len() - 1 => Activation::ReLU,
+                (None, None) => Activation::Linear,
+            };
+
+            let mut neurons = vec![];
+            for (row, b) in weight.iter().zip(bias) {
+                let w = numbers(row).ok_or(format!("layer {}: weight rows must be numbers", i))?;
+                let b = b
+                    .as_f64()
+                    .ok_or(format!("layer {}: bias must be numbers", i))?;
+                if *nin.get_or_insert(w.len()) != w.len() {
+                    return Err(
+                        format!("layer {}: expected {} inputs per row", i, nin.unwrap()).into(),
+                    );
+                }
+                neurons.push(Neuron::with_weights(w, b, activation));
+            }
+
+            nin = Some(neurons.len());
+            layers.push(Layer::from_neurons(neurons));
+        }
+
+        Ok(MLP::from_layers(layers))
+    }
+}
+
+const MAGIC: &[u8] = b"RGRD";
+const FORMAT_VERSION: u32 = 1;
+// nin: u32, nout: u32, activation: u8
+const LAYER_HEADER_LEN: usize = 9;
+
+fn activation_code(activation: Activation) -> u8 {
+    match activation {
+        Activation::Linear => 0,
+        Activation::ReLU => 1,
+        Activation::Tanh => 2,
+        Activation::Sigmoid => 3,
+    }
+}
+
+fn activation_from_code(code: u8) -> Option<Activation> {
+    match code {
+        0 => Some(Activation::Linear),
+        1 => Some(Activation::ReLU),
+        2 => Some(Activation::Tanh),
+        3 => Some(Activation::Sigmoid),
+        _ => None,
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        if self.bytes.len() < n {
+            return Err("unexpected end of file".into());
+        }
+        let (head, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, Box<dyn Error>> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+}
+
+fn numbers(value: &Json) -> Option<Vec<f64>> {
+    value.as_array()?.iter().map(Json::as_f64).collect()
+}
+
+// `{}` on f64 prints the shortest representation that parses back exactly
+fn join(values: impl Iterator<Item = f64>) -> String {
+    values
+        .map(|v| format!("{}", v))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
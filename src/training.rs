@@ -0,0 +1,220 @@
+use crate::{
+    csv_rows, losses, read_csv_file, shuffle_in_unison, LearningRate, Optimizer, Scheduler,
+    StandardScaler, Value, MLP, SGD,
+};
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Loss {
+    /// svm "max-margin" loss for labels in `{-1, 1}`
+    Hinge,
+    /// Mean squared error between the first output and the target
+    Mse,
+}
+
+/// `(epoch, mean loss, accuracy)` observer for `FitOptions::on_epoch`.
+pub type EpochCallback = Box<dyn FnMut(usize, f64, f64)>;
+
+pub struct FitOptions {
+    pub epochs: usize,
+    pub batch_size: usize,
+    /// Queried once per epoch, with the 0-based epoch as the step
+    pub learning_rate: Box<dyn Scheduler>,
+    pub loss: Loss,
+    /// Reshuffles the data every epoch when set
+    pub shuffle_seed: Option<u64>,
+    /// `(n, path)`: saves the model with `MLP::save` after every `n`-th epoch.
+    /// A `{epoch}` placeholder in `path` is replaced by the 1-based epoch number;
+    /// without one, each checkpoint overwrites the previous.
+    pub checkpoint_every: Option<(usize, String)>,
+    /// Called by `fit` after every epoch with `(epoch, mean loss, accuracy)`,
+    /// where accuracy is the fraction of training samples whose output has the
+    /// label's sign. E.g. for logging, progress bars or early stopping.
+    pub on_epoch: Option<EpochCallback>,
+}
+
+impl Default for FitOptions {
+    fn default() -> FitOptions {
+        FitOptions {
+            epochs: 100,
+            batch_size: 32,
+            learning_rate: Box::new(LearningRate::Constant(0.1)),
+            loss: Loss::Hinge,
+            shuffle_seed: None,
+            checkpoint_every: None,
+            on_epoch: None,
+        }
+    }
+}
+
+impl MLP {
+    /// Trains with mini-batch SGD and returns the mean batch loss of every epoch.
+    /// Fails only if writing a checkpoint fails; the model keeps the weights
+    /// trained up to that point.
+    pub fn fit(
+        &self,
+        xs: &[Vec<f64>],
+        ys: &[f64],
+        opts: FitOptions,
+    ) -> Result<Vec<f64>, Box<dyn Error>> {
+        self.fit_with_l2(xs, ys, opts, 0.0)
+    }
+
+    // `fit` with `l2 * Σ p²` over all parameters added to every batch loss
+    fn fit_with_l2(
+        &self,
+        xs: &[Vec<f64>],
+        ys: &[f64],
+        mut opts: FitOptions,
+        l2: f64,
+    ) -> Result<Vec<f64>, Box<dyn Error>> {
+        assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+        let mut xs = xs.to_vec();
+        let mut ys = ys.to_vec();
+        let batch_size = opts.batch_size.max(1);
+        let mut history = vec![];
+        let mut optimizer = SGD::new(self.parameters(), 0.0);
+
+        for epoch in 0..opts.epochs {
+            if let Some(seed) = opts.shuffle_seed {
+                shuffle_in_unison(&mut xs, &mut ys, seed.wrapping_add(epoch as u64));
+            }
+            optimizer.set_lr(opts.learning_rate.lr(epoch));
+
+            let mut epoch_loss = 0.0;
+            let mut batches = 0;
+            for (xb, yb) in xs.chunks(batch_size).zip(ys.chunks(batch_size)) {
+                let loss = batch_loss(self, xb, yb, opts.loss, l2);
+
+                self.zero_grad();
+                loss.backward();
+
+                optimizer.step();
+
+                epoch_loss += loss.borrow().data;
+                batches += 1;
+            }
+            let mean_loss = epoch_loss / batches.max(1) as f64;
+            history.push(mean_loss);
+            checkpoint(self, &opts, epoch)?;
+
+            if let Some(on_epoch) = opts.on_epoch.as_mut() {
+                let correct = xs
+                    .iter()
+                    .zip(&ys)
+                    .filter(|(x, y)| (self.predict(x)[0] > 0.0) == (**y > 0.0))
+                    .count();
+                on_epoch(epoch, mean_loss, correct as f64 / xs.len().max(1) as f64);
+            }
+        }
+
+        Ok(history)
+    }
+}
+
+/// Trains `model` on a `x,y,label` CSV file without loading it into memory,
+/// making `opts.epochs` passes over the file and stepping SGD on every
+/// `opts.batch_size` rows as they are read.
+///
+/// Returns the loss of every batch in stream order. Rows cannot be shuffled or
+/// standardized in a single pass, so `shuffle_seed` is ignored and features are
+/// used as-is.
+pub fn train_streaming(
+    path: &str,
+    model: &MLP,
+    opts: FitOptions,
+) -> Result<Vec<f64>, Box<dyn Error>> {
+    let batch_size = opts.batch_size.max(1);
+    let mut history = vec![];
+    let mut optimizer = SGD::new(model.parameters(), 0.0);
+
+    for epoch in 0..opts.epochs {
+        optimizer.set_lr(opts.learning_rate.lr(epoch));
+
+        let mut rows = csv_rows(path)?.peekable();
+        while rows.peek().is_some() {
+            let mut xb = vec![];
+            let mut yb = vec![];
+            for row in rows.by_ref().take(batch_size) {
+                let row = row?;
+                xb.push(vec![row.x, row.y]);
+                yb.push(row.label);
+            }
+
+            let loss = batch_loss(model, &xb, &yb, opts.loss, 0.0);
+            model.zero_grad();
+            loss.backward();
+            optimizer.step();
+            history.push(loss.borrow().data);
+        }
+        checkpoint(model, &opts, epoch)?;
+    }
+
+    Ok(history)
+}
+
+fn checkpoint(model: &MLP, opts: &FitOptions, epoch: usize) -> Result<(), Box<dyn Error>> {
+    if let Some((every, path)) = &opts.checkpoint_every {
+        if *every > 0 && (epoch + 1).is_multiple_of(*every) {
+            let path = path.replace("{epoch}", &(epoch + 1).to_string());
+            model
+                .save(&path)
+                .map_err(|e| format!("failed to write checkpoint {}: {}", path, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Trains a binary classifier on a `x,y,label` CSV file (labels in `{-1, 1}`).
+///
+/// `arch` lists the layer sizes after the 2-feature input, as in `MLP::new`.
+/// Features are standardized before training, so new inputs to the returned
+/// model should first go through the returned scaler's `transform`. Trains the
+/// hinge loss with an L2 penalty of `0.0001`.
+pub fn train_mlp_from_csv(
+    path: &str,
+    arch: Vec<i32>,
+    epochs: usize,
+    lr: f64,
+) -> Result<(MLP, StandardScaler), Box<dyn Error>> {
+    let data_points = read_csv_file(path)?;
+    if data_points.is_empty() {
+        return Err(format!("{} contains no data rows", path).into());
+    }
+
+    let xs: Vec<Vec<f64>> = data_points.iter().map(|p| vec![p.x, p.y]).collect();
+    let ys: Vec<f64> = data_points.iter().map(|p| p.label).collect();
+    let scaler = StandardScaler::fit(&xs);
+    let xs = scaler.transform(&xs);
+
+    let model = MLP::new(2, arch);
+    let opts = FitOptions {
+        epochs,
+        batch_size: xs.len(),
+        learning_rate: Box::new(LearningRate::Constant(lr)),
+        ..FitOptions::default()
+    };
+    model.fit_with_l2(&xs, &ys, opts, 0.0001)?;
+
+    Ok((model, scaler))
+}
+
+fn batch_loss(model: &MLP, xs: &[Vec<f64>], ys: &[f64], loss: Loss, l2: f64) -> Value {
+    let n = ys.len() as f64;
+    let data_loss = ys
+        .iter()
+        .zip(xs)
+        .map(|(yi, xrow)| {
+            let score = &model.forward_f64(xrow)[0];
+            match loss {
+                Loss::Hinge => (1.0 + -yi * score).relu(),
+                Loss::Mse => (score - Value::from(*yi)).pow(2.0),
+            }
+        })
+        .sum::<Value>()
+        / n;
+    if l2 == 0.0 {
+        return data_loss;
+    }
+    data_loss + l2 * losses::sum_of_squares(&model.parameters())
+}
@@ -0,0 +1,102 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+
+/// How a single hyperparameter is sampled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Range {
+    Uniform(f64, f64),
+    /// Uniform in log space, for scale parameters such as learning rates.
+    LogUniform(f64, f64),
+    Choice(Vec<f64>),
+}
+
+impl Range {
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        match self {
+            Range::Uniform(low, high) => rng.gen_range(*low..=*high),
+            Range::LogUniform(low, high) => rng.gen_range(low.ln()..=high.ln()).exp(),
+            Range::Choice(options) => options[rng.gen_range(0..options.len())],
+        }
+    }
+}
+
+/// Named hyperparameters to search over, e.g.
+/// `SearchSpace::new(0).log_uniform("lr", 1e-4, 1e-1).choice("width", vec![8.0, 16.0])`.
+#[derive(Debug, Clone)]
+pub struct SearchSpace {
+    dims: Vec<(String, Range)>,
+    seed: u64,
+}
+
+impl SearchSpace {
+    pub fn new(seed: u64) -> SearchSpace {
+        SearchSpace { dims: vec![], seed }
+    }
+
+    pub fn param(mut self, name: &str, range: Range) -> SearchSpace {
+        self.dims.push((String::from(name), range));
+        self
+    }
+
+    pub fn uniform(self, name: &str, low: f64, high: f64) -> SearchSpace {
+        self.param(name, Range::Uniform(low, high))
+    }
+
+    pub fn log_uniform(self, name: &str, low: f64, high: f64) -> SearchSpace {
+        self.param(name, Range::LogUniform(low, high))
+    }
+
+    pub fn choice(self, name: &str, options: Vec<f64>) -> SearchSpace {
+        assert!(!options.is_empty(), "choice {:?} has no options", name);
+        self.param(name, Range::Choice(options))
+    }
+}
+
+/// One sampled configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hyperparams {
+    values: HashMap<String, f64>,
+}
+
+impl Hyperparams {
+    /// Panics if `name` is not part of the search space.
+    pub fn get(&self, name: &str) -> f64 {
+        *self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("unknown hyperparameter {:?}", name))
+    }
+}
+
+/// Evaluates `trials` random configurations from `space` and returns the one
+/// with the lowest `eval_fn` (validation loss), along with that loss.
+///
+/// Sampling is reproducible for a given `SearchSpace` seed. `NaN` losses never win.
+pub fn random_search<F: Fn(&Hyperparams) -> f64>(
+    space: &SearchSpace,
+    trials: usize,
+    eval_fn: F,
+) -> (Hyperparams, f64) {
+    assert!(trials > 0, "random_search needs at least one trial");
+    let mut rng = StdRng::seed_from_u64(space.seed);
+
+    let mut best: Option<(Hyperparams, f64)> = None;
+    for _ in 0..trials {
+        let params = Hyperparams {
+            values: space
+                .dims
+                .iter()
+                .map(|(name, range)| (name.clone(), range.sample(&mut rng)))
+                .collect(),
+        };
+        let loss = eval_fn(&params);
+        let improves = match &best {
+            Some((_, best_loss)) => loss < *best_loss || best_loss.is_nan(),
+            None => true,
+        };
+        if improves {
+            best = Some((params, loss));
+        }
+    }
+    best.unwrap()
+}
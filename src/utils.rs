@@ -1,3 +1,4 @@
+use rand::{rngs::StdRng, seq::index, Rng, SeedableRng};
 use std::error::Error;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -9,30 +10,29 @@ pub struct DataPoint {
 }
 
 pub fn read_csv_file(filename: &str) -> Result<Vec<DataPoint>, Box<dyn Error>> {
+    csv_rows(filename)?.collect()
+}
+
+/// Lazily parses a `x,y,label` CSV file one row at a time, skipping the header,
+/// so large files can be consumed without loading them into memory.
+pub fn csv_rows(
+    filename: &str,
+) -> Result<impl Iterator<Item = Result<DataPoint, Box<dyn Error>>>, Box<dyn Error>> {
     let file = File::open(filename)?;
     let reader = BufReader::new(file);
 
-    let mut data_points = vec![];
-
-    for (index, line) in reader.lines().enumerate() {
-        let line = line?;
-
-        if index == 0 {
-            // Skip the header row
-            continue;
-        }
-
-        let fields: Vec<&str> = line.split(',').collect();
+    // Skip the header row
+    Ok(reader.lines().skip(1).map(|line| parse_row(&line?)))
+}
 
-        let x = fields[0].parse::<f64>()?;
-        let y = fields[1].parse::<f64>()?;
-        let label = fields[2].parse::<f64>()?;
+fn parse_row(line: &str) -> Result<DataPoint, Box<dyn Error>> {
+    let fields: Vec<&str> = line.split(',').collect();
 
-        let data_point = DataPoint { x, y, label };
-        data_points.push(data_point);
-    }
+    let x = fields[0].parse::<f64>()?;
+    let y = fields[1].parse::<f64>()?;
+    let label = fields[2].parse::<f64>()?;
 
-    Ok(data_points)
+    Ok(DataPoint { x, y, label })
 }
 
 pub fn load_moons_data() -> (Vec<Vec<f64>>, Vec<f64>) {
@@ -48,3 +48,159 @@ pub fn load_moons_data() -> (Vec<Vec<f64>>, Vec<f64>) {
 
     (xs, ys)
 }
+
+pub fn shuffle_in_unison(xs: &mut [Vec<f64>], ys: &mut [f64], seed: u64) {
+    assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // Fisher-Yates, applying every swap to both slices
+    for i in (1..xs.len()).rev() {
+        let j = rng.gen_range(0..=i);
+        xs.swap(i, j);
+        ys.swap(i, j);
+    }
+}
+
+/// Draws `round(frac * n)` distinct samples, reproducibly for a given seed.
+/// The subset keeps the original relative order.
+pub fn subsample(xs: &[Vec<f64>], ys: &[f64], frac: f64, seed: u64) -> (Vec<Vec<f64>>, Vec<f64>) {
+    assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+    let n = xs.len();
+    let k = ((frac.clamp(0.0, 1.0) * n as f64).round() as usize).min(n);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut picked = index::sample(&mut rng, n, k).into_vec();
+    picked.sort_unstable();
+
+    let sub_xs = picked.iter().map(|&i| xs[i].clone()).collect();
+    let sub_ys = picked.iter().map(|&i| ys[i]).collect();
+    (sub_xs, sub_ys)
+}
+
+/// Adds independent `N(0, stddev²)` noise to every feature, reproducibly for a given seed.
+pub fn jitter(xs: &mut [Vec<f64>], stddev: f64, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    for x in xs.iter_mut() {
+        for xi in x.iter_mut() {
+            *xi += sample_normal(&mut rng, 0.0, stddev);
+        }
+    }
+}
+
+/// Rotates 2D points counter-clockwise about the origin.
+pub fn rotate2d(xs: &mut [Vec<f64>], radians: f64) {
+    let (sin, cos) = radians.sin_cos();
+    for x in xs.iter_mut() {
+        assert_eq!(x.len(), 2, "rotate2d expects 2D points");
+        let (a, b) = (x[0], x[1]);
+        x[0] = a * cos - b * sin;
+        x[1] = a * sin + b * cos;
+    }
+}
+
+/// Expands each row into all monomials of degree `1..=degree`, grouped by
+/// degree, e.g. `[x, y]` with degree 2 becomes `[x, y, x², xy, y²]`.
+pub fn polynomial_features(xs: &[Vec<f64>], degree: usize) -> Vec<Vec<f64>> {
+    let nfeatures = xs.first().map_or(0, |x| x.len());
+    let mut terms: Vec<Vec<usize>> = vec![];
+    let mut current: Vec<Vec<usize>> = vec![vec![]];
+    for _ in 0..degree {
+        // Non-decreasing index tuples, so each monomial appears once
+        current = current
+            .iter()
+            .flat_map(|term| {
+                let start = term.last().copied().unwrap_or(0);
+                (start..nfeatures).map(move |i| [term.as_slice(), &[i]].concat())
+            })
+            .collect();
+        terms.extend(current.iter().cloned());
+    }
+
+    xs.iter()
+        .map(|x| {
+            assert_eq!(x.len(), nfeatures, "all rows must have the same length");
+            terms
+                .iter()
+                .map(|term| term.iter().map(|&i| x[i]).product())
+                .collect()
+        })
+        .collect()
+}
+
+/// Mixup augmentation: draws one `λ ~ Beta(alpha, alpha)` and returns
+/// `λ·(x_i, y_i) + (1 - λ)·(x_j, y_j)` for each sample `i` and a randomly
+/// permuted partner `j`. Labels are blended too, so they may be fractional.
+///
+/// As in the original paper, `alpha <= 0` disables mixing (`λ = 1`).
+pub fn mixup(xs: &[Vec<f64>], ys: &[f64], alpha: f64, seed: u64) -> (Vec<Vec<f64>>, Vec<f64>) {
+    assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let lambda = if alpha > 0.0 {
+        sample_beta(&mut rng, alpha, alpha)
+    } else {
+        1.0
+    };
+    let partners = index::sample(&mut rng, xs.len(), xs.len()).into_vec();
+
+    let mixed_xs = xs
+        .iter()
+        .zip(&partners)
+        .map(|(xi, &j)| {
+            xi.iter()
+                .zip(&xs[j])
+                .map(|(a, b)| lambda * a + (1.0 - lambda) * b)
+                .collect()
+        })
+        .collect();
+    let mixed_ys = ys
+        .iter()
+        .zip(&partners)
+        .map(|(yi, &j)| lambda * yi + (1.0 - lambda) * ys[j])
+        .collect();
+    (mixed_xs, mixed_ys)
+}
+
+// Beta(a, b) as X / (X + Y) with X ~ Gamma(a), Y ~ Gamma(b)
+fn sample_beta<R: Rng>(rng: &mut R, a: f64, b: f64) -> f64 {
+    let x = sample_gamma(rng, a);
+    let y = sample_gamma(rng, b);
+    if x + y == 0.0 {
+        // Both underflowed, which only happens for tiny shapes where Beta is ~{0, 1}
+        return if rng.gen::<bool>() { 1.0 } else { 0.0 };
+    }
+    x / (x + y)
+}
+
+// Marsaglia-Tsang, boosting shapes below 1 via Gamma(k) = Gamma(k + 1)·U^(1/k)
+fn sample_gamma<R: Rng>(rng: &mut R, shape: f64) -> f64 {
+    if shape < 1.0 {
+        let u: f64 = rng.gen();
+        return sample_gamma(rng, shape + 1.0) * u.powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let z = sample_normal(rng, 0.0, 1.0);
+        let v = (1.0 + c * z).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u: f64 = 1.0 - rng.gen::<f64>();
+        if u.ln() < 0.5 * z * z + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// A generator for the `*_with_rng` constructors: passing one seeded
+/// generator down through every stochastic step makes a whole run reproducible.
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+// Box-Muller transform, since rand's normal distribution lives in rand_distr
+pub(crate) fn sample_normal<R: Rng>(rng: &mut R, mean: f64, std: f64) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    mean + std * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
@@ -0,0 +1,124 @@
+use crate::engine::{pairwise_sum, ValueData};
+use crate::{losses, Value};
+
+/// Adds `s` to every element. `s` feeds every output, so its grad accumulates from all of them.
+pub fn add_scalar(v: &[Value], s: &Value) -> Vec<Value> {
+    v.iter().map(|vi| vi + s).collect()
+}
+
+/// `.relu()` of every element.
+pub fn relu_vec(v: &[Value]) -> Vec<Value> {
+    v.iter().map(Value::relu).collect()
+}
+
+/// `.tanh()` of every element.
+pub fn tanh_vec(v: &[Value]) -> Vec<Value> {
+    v.iter().map(Value::tanh).collect()
+}
+
+/// `.sigmoid()` of every element.
+pub fn sigmoid_vec(v: &[Value]) -> Vec<Value> {
+    v.iter().map(Value::sigmoid).collect()
+}
+
+/// Sum of `vs` as a balanced tree of `+` nodes, so the graph is `log n` deep
+/// instead of the `n`-deep chain `Sum` builds, with less rounding error.
+/// Panics if `vs` is empty.
+pub fn sum_balanced(vs: &[Value]) -> Value {
+    pairwise_sum(vs)
+}
+
+/// Maximum of `vs` as a single node. The whole upstream gradient goes to the
+/// (first) largest element; the others receive none.
+pub fn max_reduce(vs: &[Value]) -> Value {
+    let max = vs
+        .iter()
+        .map(|v| v.borrow().data)
+        .reduce(f64::max)
+        .expect("must contain at least one Value");
+
+    Value::from_op(max, vs.to_vec(), "max", |value: &ValueData| {
+        if let Some(argmax) = value._prev.iter().find(|v| v.borrow().data == value.data) {
+            argmax.borrow_mut().grad += value.grad;
+        }
+    })
+}
+
+/// The `k` largest elements by data, in descending order (ties keep their
+/// original order). These are the input nodes themselves, so gradient reaches
+/// only the selected elements. Returns everything when `k > vs.len()`.
+pub fn topk(vs: &[Value], k: usize) -> Vec<Value> {
+    let mut sorted = vs.to_vec();
+    sorted.sort_by(|a, b| b.borrow().data.total_cmp(&a.borrow().data));
+    sorted.truncate(k);
+    sorted
+}
+
+/// Sums a `batch × features` matrix over samples (`axis == 0`, one `Value`
+/// per feature) or over features (`axis == 1`, one `Value` per sample).
+pub fn sum_axis(batch: &[Vec<Value>], axis: usize) -> Vec<Value> {
+    match axis {
+        0 => {
+            let nfeatures = batch.first().map_or(0, |row| row.len());
+            assert!(
+                batch.iter().all(|row| row.len() == nfeatures),
+                "all rows must have the same length"
+            );
+            (0..nfeatures)
+                .map(|j| batch.iter().map(|row| &row[j]).sum())
+                .collect()
+        }
+        1 => batch.iter().map(|row| row.iter().sum()).collect(),
+        _ => panic!("axis must be 0 or 1, got {}", axis),
+    }
+}
+
+/// Differentiable histogram: each input spreads one unit of mass over the bins
+/// by a softmax of Gaussian kernels `-(v - c)² / (2·bandwidth²)`, so the counts
+/// always total `vs.len()` and every input receives gradient.
+pub fn soft_histogram(vs: &[Value], centers: &[f64], bandwidth: f64) -> Vec<Value> {
+    assert!(!centers.is_empty(), "need at least one bin center");
+    assert!(
+        bandwidth > 0.0,
+        "bandwidth must be positive, got {}",
+        bandwidth
+    );
+    if vs.is_empty() {
+        return centers.iter().map(|_| Value::from(0.0)).collect();
+    }
+
+    let scale = -0.5 / (bandwidth * bandwidth);
+    let memberships: Vec<Vec<Value>> = vs
+        .iter()
+        .map(|v| {
+            let logits: Vec<Value> = centers
+                .iter()
+                .map(|&c| (v + (-c)).pow(2.0) * scale)
+                .collect();
+            // Shifting by the largest logit keeps far-away inputs from underflowing to 0 / 0
+            let shift = logits
+                .iter()
+                .map(|l| l.borrow().data)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let weights: Vec<Value> = logits.iter().map(|l| (l + (-shift)).exp()).collect();
+            let total: Value = weights.iter().sum();
+            weights.iter().map(|w| w / &total).collect()
+        })
+        .collect();
+
+    (0..centers.len())
+        .map(|k| memberships.iter().map(|m| &m[k]).sum())
+        .collect()
+}
+
+/// `dot(a, b) / (||a|| * ||b||)` as a graph, with gradients to both vectors.
+/// A tiny epsilon under each square root keeps zero vectors from producing
+/// `NaN` (their similarity is `0`).
+pub fn cosine_similarity(a: &[Value], b: &[Value]) -> Value {
+    assert_eq!(a.len(), b.len(), "vectors must have the same length");
+    assert!(!a.is_empty(), "vectors must not be empty");
+
+    let dot: Value = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[Value]| (losses::sum_of_squares(v) + 1e-12).pow(0.5);
+    dot / (norm(a) * norm(b))
+}
@@ -0,0 +1,300 @@
+use crate::Value;
+#[cfg(feature = "png")]
+use crate::MLP;
+use std::collections::HashMap;
+#[cfg(feature = "png")]
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Unary,
+    Binary,
+    Variadic,
+}
+
+/// Display metadata for an `_op` string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpInfo {
+    pub op: &'static str,
+    pub symbol: &'static str,
+    /// Number of differentiable inputs; constant parameters threaded through
+    /// `_prev` (like `pow`'s exponent or `threshold`'s bounds) are not counted.
+    pub arity: Arity,
+}
+
+pub const OPS: &[OpInfo] = &[
+    OpInfo {
+        op: "+",
+        symbol: "+",
+        arity: Arity::Binary,
+    },
+    OpInfo {
+        op: "×",
+        symbol: "×",
+        arity: Arity::Binary,
+    },
+    OpInfo {
+        op: "/",
+        symbol: "÷",
+        arity: Arity::Binary,
+    },
+    OpInfo {
+        op: "^",
+        symbol: "^",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "ReLU",
+        symbol: "ReLU",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "Threshold",
+        symbol: "θ",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "Heaviside",
+        symbol: "H",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "FakeQuant",
+        symbol: "Q",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "PiecewiseLinear",
+        symbol: "pwl",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "ClipGrad",
+        symbol: "clip",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "StopGradient",
+        symbol: "sg",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "tanh",
+        symbol: "tanh",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "sigmoid",
+        symbol: "σ",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "atan",
+        symbol: "atan",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "asin",
+        symbol: "asin",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "acos",
+        symbol: "acos",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "exp",
+        symbol: "exp",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "log",
+        symbol: "log",
+        arity: Arity::Unary,
+    },
+    OpInfo {
+        op: "fma",
+        symbol: "fma",
+        arity: Arity::Variadic,
+    },
+    OpInfo {
+        op: "max",
+        symbol: "max",
+        arity: Arity::Variadic,
+    },
+];
+
+pub fn op_info(op: &str) -> Option<&'static OpInfo> {
+    OPS.iter().find(|info| info.op == op)
+}
+
+/// Renders the graph rooted at `value` in graphviz dot format. Every op gets
+/// its own node labelled with its registered symbol, shaped by arity.
+pub fn to_dot(value: &Value) -> String {
+    let mut dot = String::from("digraph {\n    rankdir=\"LR\"\n");
+    dot_node(value, &mut dot, &mut HashMap::new());
+    dot.push_str("}\n");
+    dot
+}
+
+fn dot_node(value: &Value, dot: &mut String, ids: &mut HashMap<u64, usize>) -> usize {
+    let v = value.borrow();
+    if let Some(&id) = ids.get(&v.id) {
+        return id;
+    }
+    let id = ids.len();
+    ids.insert(v.id, id);
+
+    let name = v
+        .label
+        .as_deref()
+        .map(|l| format!("{} | ", l))
+        .unwrap_or_default();
+    dot.push_str(&format!(
+        "    {} [shape=box label=\"{}data={:.4} grad={:.4}\"]\n",
+        id, name, v.data, v.grad
+    ));
+
+    if let Some(op) = v._op.as_deref() {
+        let (symbol, shape) = match op_info(op) {
+            Some(info) => (info.symbol, shape(info.arity)),
+            None => (op, "ellipse"),
+        };
+        dot.push_str(&format!(
+            "    {}_op [shape={} label=\"{}\"]\n",
+            id, shape, symbol
+        ));
+        dot.push_str(&format!("    {}_op -> {}\n", id, id));
+        for child in v._prev.iter() {
+            let child_id = dot_node(child, dot, ids);
+            dot.push_str(&format!("    {} -> {}_op\n", child_id, id));
+        }
+    }
+    id
+}
+
+fn shape(arity: Arity) -> &'static str {
+    match arity {
+        Arity::Unary => "ellipse",
+        Arity::Binary => "circle",
+        Arity::Variadic => "diamond",
+    }
+}
+
+// Binding strength of a rendered expression, used to decide on parentheses
+const SUM: u8 = 0;
+const PRODUCT: u8 = 1;
+const ATOM: u8 = 2;
+
+/// Renders the expression graph rooted at `value` as a LaTeX math expression.
+/// Intended for small graphs: shared subexpressions are expanded at every use.
+pub fn to_latex(value: &Value) -> String {
+    latex(value).0
+}
+
+fn latex(value: &Value) -> (String, u8) {
+    let v = value.borrow();
+    if let Some(label) = &v.label {
+        return (label.clone(), ATOM);
+    }
+    let op = match v._op.as_deref() {
+        Some(op) => op,
+        None if v.data < 0.0 => return (format!("{}", v.data), PRODUCT),
+        None => return (format!("{}", v.data), ATOM),
+    };
+
+    let group = |child: &Value, min: u8| {
+        let (expr, prec) = latex(child);
+        if prec < min {
+            format!("\\left({}\\right)", expr)
+        } else {
+            expr
+        }
+    };
+
+    match op {
+        "+" => {
+            let lhs = group(&v._prev[0], SUM);
+            match negated(&v._prev[1]) {
+                Some(rhs) => (format!("{} - {}", lhs, group(&rhs, PRODUCT)), SUM),
+                None => (format!("{} + {}", lhs, group(&v._prev[1], SUM)), SUM),
+            }
+        }
+        "×" => {
+            if let Some(den) = reciprocal(&v._prev[1]) {
+                let num = latex(&v._prev[0]).0;
+                return (format!("\\frac{{{}}}{{{}}}", num, latex(&den).0), ATOM);
+            }
+            if let Some(x) = negated(value) {
+                return (format!("-{}", group(&x, ATOM)), PRODUCT);
+            }
+            let lhs = group(&v._prev[0], PRODUCT);
+            let rhs = group(&v._prev[1], ATOM);
+            (format!("{} \\cdot {}", lhs, rhs), PRODUCT)
+        }
+        "/" => {
+            let num = latex(&v._prev[0]).0;
+            (
+                format!("\\frac{{{}}}{{{}}}", num, latex(&v._prev[1]).0),
+                ATOM,
+            )
+        }
+        "^" => {
+            let base = group(&v._prev[0], ATOM);
+            (format!("{}^{{{}}}", base, latex(&v._prev[1]).0), ATOM)
+        }
+        _ => {
+            let args: Vec<String> = v._prev.iter().map(|p| latex(p).0).collect();
+            let name = format!("\\mathrm{{{}}}", op);
+            (format!("{}\\left({}\\right)", name, args.join(", ")), ATOM)
+        }
+    }
+}
+
+// `a / b` is built as `a × b^-1`; returns `b` for such a `b^-1` node
+fn reciprocal(value: &Value) -> Option<Value> {
+    let v = value.borrow();
+    let is_inverse = v._op.as_deref() == Some("^") && v._prev[1].borrow().data == -1.0;
+    is_inverse.then(|| v._prev[0].clone())
+}
+
+// `-a` is built as `a × -1`; returns `a` for such a node
+fn negated(value: &Value) -> Option<Value> {
+    let v = value.borrow();
+    let is_negation = v._op.as_deref() == Some("×")
+        && v._prev[1].borrow()._op.is_none()
+        && v._prev[1].borrow().label.is_none()
+        && v._prev[1].borrow().data == -1.0;
+    is_negation.then(|| v._prev[0].clone())
+}
+
+/// Renders `MLP::decision_grid` as a `resolution × resolution` PNG heatmap.
+/// Negative scores are shaded blue and positive scores red.
+#[cfg(feature = "png")]
+pub fn save_decision_png(
+    model: &MLP,
+    bound: f64,
+    resolution: usize,
+    path: &str,
+) -> Result<(), Box<dyn Error>> {
+    let grid = model.decision_grid(bound, resolution);
+    let scale = grid
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |acc, v| acc.max(v.abs()))
+        .max(f64::EPSILON);
+
+    let size = resolution as u32;
+    let image = image::RgbImage::from_fn(size, size, |x, y| {
+        let t = (grid[y as usize][x as usize] / scale).clamp(-1.0, 1.0);
+        let fade = (255.0 * (1.0 - t.abs())) as u8;
+        if t > 0.0 {
+            image::Rgb([255, fade, fade])
+        } else {
+            image::Rgb([fade, fade, 255])
+        }
+    });
+    image.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{adversarial, MlpBuilder};
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn fgsm_moves_score_against_target() {
+    let model = MlpBuilder::new(3).linear(1).build();
+    // Parameters are [bias, w0, w1, w2]; w2 = 0 has no influence on the score
+    for (p, v) in model.parameters().iter().zip([0.1, 2.0, -0.5, 0.0]) {
+        p.borrow_mut().data = v;
+    }
+    let x = [0.3, 0.4, -0.2];
+    let score = model.predict(&x)[0];
+
+    let adv = adversarial::fgsm(&model, &x, 1.0, 0.1);
+    assert!((adv[0] - 0.2).abs() < EPS);
+    assert!((adv[1] - 0.5).abs() < EPS);
+    assert_eq!(adv[2], -0.2);
+    // Moving every feature by 0.1 against the gradient costs 0.1 * (|w0| + |w1|)
+    assert!((model.predict(&adv)[0] - (score - 0.25)).abs() < EPS);
+
+    let adv = adversarial::fgsm(&model, &x, -1.0, 0.1);
+    assert!(model.predict(&adv)[0] > score);
+}
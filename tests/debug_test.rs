@@ -0,0 +1,33 @@
+#![cfg(feature = "std")]
+
+use rustygrad::debug::ActivationStats;
+use rustygrad::{Activation, Value};
+
+const EPS: f64 = 0.0001;
+
+fn values(data: &[f64]) -> Vec<Value> {
+    data.iter().map(|&d| Value::from(d)).collect()
+}
+
+#[test]
+fn stats_on_relu_outputs() {
+    let stats = ActivationStats::new(&values(&[0.0, 0.0, 1.0, 3.0]), Activation::ReLU);
+    assert_eq!(stats.min, 0.0);
+    assert_eq!(stats.max, 3.0);
+    assert!((stats.mean - 1.0).abs() < EPS);
+    // Deviations -1, -1, 0, 2: variance 6 / 4
+    assert!((stats.std - 1.5_f64.sqrt()).abs() < EPS);
+    assert_eq!(stats.dead, 0.5);
+    assert_eq!(stats.saturated, 0.0);
+}
+
+#[test]
+fn stats_on_bounded_outputs() {
+    let tanh = ActivationStats::new(&values(&[-0.999, 0.2, 0.995, 0.0]), Activation::Tanh);
+    assert_eq!(tanh.saturated, 0.5);
+    assert_eq!(tanh.dead, 0.0);
+
+    let sigmoid =
+        ActivationStats::new(&values(&[0.001, 0.5, 0.6, 0.7, 0.999]), Activation::Sigmoid);
+    assert!((sigmoid.saturated - 0.4).abs() < EPS);
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{ensemble, MLP};
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn ensemble_of_identical_models_matches_single_model() {
+    let a = MLP::seeded(3, vec![4, 2], 21);
+    let b = MLP::seeded(3, vec![4, 2], 21);
+    let x = [0.3, -1.2, 0.8];
+
+    let single = a.predict(&x);
+    let averaged = ensemble::predict(&[&a, &b], &x);
+    assert_eq!(averaged.len(), 2);
+    for (s, e) in single.iter().zip(&averaged) {
+        assert!((s - e).abs() < EPS);
+    }
+}
+
+#[test]
+fn ensemble_averages_predictions() {
+    let a = MLP::seeded(3, vec![4, 2], 1);
+    let b = MLP::seeded(3, vec![4, 2], 2);
+    let x = [0.3, -1.2, 0.8];
+
+    let (pa, pb) = (a.predict(&x), b.predict(&x));
+    let averaged = ensemble::predict(&[&a, &b], &x);
+    for i in 0..2 {
+        assert!((averaged[i] - (pa[i] + pb[i]) / 2.0).abs() < EPS);
+    }
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{gradcheck, Value};
+
+#[test]
+fn complex_step_matches_backward() {
+    let x = Value::from(1.3);
+    let y = &x * x.relu();
+    y.backward();
+
+    let d = gradcheck::complex_step(&y, &x).unwrap();
+    assert!((d - x.borrow().grad).abs() < 1e-12);
+    assert!((d - 2.6).abs() < 1e-12);
+}
+
+#[test]
+fn complex_step_on_composite_graph() {
+    let a = Value::from(0.7);
+    let b = Value::from(-1.1);
+    let g = ((&a * &b).exp() + (&a / &b).pow(2.0)).log() + (&a - &b).relu();
+    g.backward();
+
+    for leaf in [&a, &b] {
+        let d = gradcheck::complex_step(&g, leaf).unwrap();
+        assert!((d - leaf.borrow().grad).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn complex_step_matches_backward_at_the_asin_acos_domain_edge() {
+    for x in [1.0, -1.0] {
+        for acos in [false, true] {
+            let a = Value::from(x);
+            let y = if acos { a.acos() } else { a.asin() };
+            y.backward();
+
+            let d = gradcheck::complex_step(&y, &a).unwrap();
+            assert!(d.is_finite());
+            assert!((d - a.borrow().grad).abs() <= 1e-12 * d.abs());
+        }
+    }
+}
+
+fn model(p: &[Value]) -> Value {
+    // A small two-unit network whose parameters are all the inputs
+    let x = [Value::from(0.3), Value::from(-0.8)];
+    let h0 = (&p[0] * &x[0] + &p[1] * &x[1] + &p[2]).tanh();
+    let h1 = (&p[3] * &x[0] + &p[4] * &x[1] + &p[5]).sigmoid();
+    (&p[6] * h0 + &p[7] * h1 + &p[8]).pow(2.0)
+}
+
+const PARAMS: [f64; 9] = [0.5, -0.3, 0.1, 0.9, 0.2, -0.4, 1.2, -0.7, 0.05];
+
+#[test]
+fn grad_check_agrees_with_backward() {
+    let checks = gradcheck::grad_check(model, &PARAMS, 1e-5);
+    assert_eq!(checks.len(), PARAMS.len());
+    for (analytic, numeric) in checks {
+        assert!((analytic - numeric).abs() < 1e-6);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_grad_check_matches_serial() {
+    let serial = gradcheck::grad_check(model, &PARAMS, 1e-5);
+    let parallel = gradcheck::grad_check_parallel(model, &PARAMS, 1e-5);
+    assert_eq!(serial, parallel);
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{init, MLP};
+
+#[test]
+fn zeros_and_kaiming() {
+    let model = MLP::new(4, vec![8, 1]);
+    let params = model.parameters();
+
+    init::zeros_(&params);
+    assert!(model.parameters_iter().all(|p| p.borrow().data == 0.0));
+
+    init::kaiming_uniform_(&params, 4);
+    let bound = (6.0_f64 / 4.0).sqrt();
+    assert!(params.iter().all(|p| p.borrow().data.abs() <= bound));
+    assert!(params.iter().any(|p| p.borrow().data != 0.0));
+
+    init::kaiming_uniform_seeded_(&params, 4, 3);
+    let first: Vec<f64> = params.iter().map(|p| p.borrow().data).collect();
+    init::kaiming_uniform_seeded_(&params, 4, 3);
+    assert!(params
+        .iter()
+        .zip(&first)
+        .all(|(p, x)| p.borrow().data == *x));
+}
+
+#[test]
+#[should_panic(expected = "positive fan-in")]
+fn kaiming_rejects_empty_fan_in() {
+    let model = MLP::new(4, vec![1]);
+    init::kaiming_uniform_(&model.parameters(), 0);
+}
+
+#[test]
+fn normal_respects_seed() {
+    let a = MLP::new(4, vec![8, 1]).parameters();
+    let b = MLP::new(4, vec![8, 1]).parameters();
+
+    init::normal_(&a, 1.0, 0.1, 11);
+    init::normal_(&b, 1.0, 0.1, 11);
+    assert!(a
+        .iter()
+        .zip(&b)
+        .all(|(x, y)| x.borrow().data == y.borrow().data));
+
+    init::normal_(&b, 1.0, 0.1, 12);
+    assert!(a
+        .iter()
+        .zip(&b)
+        .any(|(x, y)| x.borrow().data != y.borrow().data));
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{Layer, MlpBuilder, Value};
+
+#[test]
+fn linear_layer_is_unsquashed() {
+    let layer = Layer::linear(1, 1);
+    let params = layer.parameters();
+    // Parameters are [bias, weight]
+    params[0].borrow_mut().data = -1.0;
+    params[1].borrow_mut().data = 2.0;
+
+    let x = vec![Value::from(-3.0)];
+    let out = &layer.forward(&x)[0];
+    assert_eq!(out.borrow().data, -7.0);
+
+    out.backward();
+    assert_eq!(x[0].borrow().grad, 2.0);
+}
+
+#[test]
+fn builder_places_linear_layers_anywhere() {
+    // 3 -> 4 (ReLU) -> 2 (linear bottleneck) -> 4 (ReLU) -> 1 (linear)
+    let model = MlpBuilder::new(3)
+        .relu(4)
+        .linear(2)
+        .relu(4)
+        .linear(1)
+        .build();
+
+    assert_eq!(
+        format!("{:?}", model),
+        "MLP { layers: [Layer { neurons: [ReLU(3), ReLU(3), ReLU(3), ReLU(3)] }, \
+         Layer { neurons: [Linear(4), Linear(4)] }, \
+         Layer { neurons: [ReLU(2), ReLU(2), ReLU(2), ReLU(2)] }, \
+         Layer { neurons: [Linear(4)] }] }"
+    );
+    let x = vec![Value::from(1.0), Value::from(2.0), Value::from(3.0)];
+    assert_eq!(model.forward(x).len(), 1);
+}
+
+#[test]
+fn forward_with_mask_matches_preactivation_sign() {
+    let layer = Layer::new(2, 16, true);
+    let x = vec![Value::from(0.7), Value::from(-1.3)];
+    let (out, mask) = layer.forward_with_mask(&x);
+    assert_eq!(out.len(), 16);
+
+    // Recompute the pre-activations by hand from [bias, w0, w1] per neuron
+    let params = layer.parameters();
+    for (i, chunk) in params.chunks(3).enumerate() {
+        let data: Vec<f64> = chunk.iter().map(|p| p.borrow().data).collect();
+        let preact = data[0] + data[1] * 0.7 + data[2] * -1.3;
+        assert_eq!(mask[i], preact > 0.0);
+        assert_eq!(out[i].borrow().data > 0.0, mask[i]);
+    }
+}
+
+#[test]
+fn layer_norm_standardizes_features() {
+    use rustygrad::{gradcheck, LayerNorm};
+
+    let norm = LayerNorm::new(4, 1e-8);
+    let x: Vec<Value> = [2.0, -1.0, 0.5, 4.0]
+        .iter()
+        .map(|&v| Value::from(v))
+        .collect();
+    let out = norm.forward(&x);
+
+    let data: Vec<f64> = out.iter().map(|o| o.borrow().data).collect();
+    let mean = data.iter().sum::<f64>() / 4.0;
+    let var = data.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / 4.0;
+    assert!(mean.abs() < 1e-6);
+    assert!((var - 1.0).abs() < 1e-6);
+
+    // Gradients flow through the mean and variance as well as the affine params
+    let loss = out
+        .iter()
+        .zip([1.0, -2.0, 0.5, 3.0])
+        .map(|(o, c)| o * c)
+        .sum::<Value>();
+    loss.backward();
+    for xi in &x {
+        let expected = gradcheck::complex_step(&loss, xi).unwrap();
+        assert!((xi.borrow().grad - expected).abs() < 1e-9);
+    }
+    assert!(norm.parameters().iter().all(|p| p.borrow().grad != 0.0));
+}
+
+#[test]
+fn jacobian_of_linear_layer_is_weight_matrix() {
+    use rustygrad::jacobian;
+
+    let layer = Layer::linear(3, 2);
+    let x: Vec<Value> = [1.0, -2.0, 0.5].iter().map(|&v| Value::from(v)).collect();
+    let out = layer.forward(&x);
+
+    // Parameters come as [bias, w0, w1, w2] per output neuron
+    let params = layer.parameters();
+    let expected: Vec<Vec<f64>> = params
+        .chunks(4)
+        .map(|c| c[1..].iter().map(|w| w.borrow().data).collect())
+        .collect();
+
+    let j = jacobian(&out, &x);
+    assert_eq!(j.len(), 2);
+    for (row, exp) in j.iter().zip(&expected) {
+        for (a, b) in row.iter().zip(exp) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn weights_matrix_is_nout_by_nin() {
+    let layer = Layer::new(3, 5, true);
+    let weights = layer.weights();
+    assert_eq!(weights.len(), 5);
+    assert!(weights.iter().all(|row| row.len() == 3));
+    assert_eq!(layer.biases(), vec![0.0; 5]);
+
+    // Rows follow the neuron parameter order: [b, w0, w1, w2] per neuron
+    let params = layer.parameters();
+    assert_eq!(weights[1][2], params[4 + 3].borrow().data);
+}
+
+#[test]
+fn forward_train_applies_inverted_dropout() {
+    let layer = Layer::linear(2, 2000);
+    for p in layer.parameters() {
+        if p.borrow().data == 0.0 {
+            // Nonzero biases, so no output is zero without being dropped
+            p.borrow_mut().data = 5.0;
+        }
+    }
+    let x = vec![Value::from(0.5), Value::from(-1.0)];
+    let plain: Vec<f64> = layer.forward(&x).iter().map(|v| v.borrow().data).collect();
+
+    let dropped = layer.forward_train(&x, 0.3, 11);
+    let zeroed = dropped.iter().filter(|v| v.borrow().data == 0.0).count();
+    assert!((zeroed as f64 / 2000.0 - 0.3).abs() < 0.05);
+    for (d, p) in dropped.iter().zip(&plain) {
+        let d = d.borrow().data;
+        assert!(d == 0.0 || (d - p / 0.7).abs() < 1e-9);
+    }
+
+    // Same seed, same mask; p = 0 is a plain forward
+    let again = layer.forward_train(&x, 0.3, 11);
+    assert!(again
+        .iter()
+        .zip(&dropped)
+        .all(|(a, b)| a.borrow().data == b.borrow().data));
+    let undropped = layer.forward_train(&x, 0.0, 11);
+    assert!(undropped
+        .iter()
+        .zip(&plain)
+        .all(|(a, p)| a.borrow().data == *p));
+}
+
+#[test]
+fn weight_norm_decouples_length_from_direction() {
+    use rustygrad::{Neuron, WeightNorm};
+
+    let wn = WeightNorm::new(Neuron::new(3, false));
+    let before: Vec<f64> = wn.weights().iter().map(|w| w.borrow().data).collect();
+    let raw: Vec<f64> = wn.v.iter().map(|v| v.borrow().data).collect();
+    for (b, r) in before.iter().zip(&raw) {
+        assert!((b - r).abs() < 1e-9);
+    }
+
+    wn.g.borrow_mut().data = -2.5;
+    for scale in [0.01, 1.0, 100.0] {
+        for (v, r) in wn.v.iter().zip(&raw) {
+            v.borrow_mut().data = r * scale;
+        }
+        let norm: f64 = wn
+            .weights()
+            .iter()
+            .map(|w| w.borrow().data.powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!((norm - 2.5).abs() < 1e-9);
+    }
+
+    let x = vec![Value::from(0.5), Value::from(-1.0), Value::from(2.0)];
+    wn.forward(&x).backward();
+    assert_ne!(wn.g.borrow().grad, 0.0);
+    assert!(wn.v.iter().any(|v| v.borrow().grad != 0.0));
+    assert_eq!(wn.parameters().len(), 5);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{losses, Value};
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn mse_multi_matches_hand_computed() {
+    let preds = vec![
+        vec![Value::from(1.0), Value::from(2.0)],
+        vec![Value::from(0.0), Value::from(-1.0)],
+    ];
+    let targets = vec![vec![0.0, 2.0], vec![2.0, 1.0]];
+
+    // (1 + 0 + 4 + 4) / 4
+    let loss = losses::mse_multi(&preds, &targets).unwrap();
+    assert!((2.25 - loss.borrow().data).abs() < EPS);
+
+    // d/dp = 2 * (p - t) / 4
+    loss.backward();
+    let grads: Vec<f64> = preds.iter().flatten().map(|p| p.borrow().grad).collect();
+    for (g, expected) in grads.iter().zip([0.5, 0.0, -1.0, -1.0]) {
+        assert!((g - expected).abs() < EPS);
+    }
+}
+
+#[test]
+fn mse_multi_rejects_mismatched_shapes() {
+    let preds = vec![vec![Value::from(1.0), Value::from(2.0)]];
+    assert!(losses::mse_multi(&preds, &[vec![1.0]]).is_err());
+    assert!(losses::mse_multi(&preds, &[vec![1.0, 2.0], vec![3.0, 4.0]]).is_err());
+    assert!(losses::mse_multi(&[], &[]).is_err());
+}
+
+#[test]
+fn cross_entropy_matches_softmax() {
+    let logits = vec![Value::from(1.0), Value::from(2.0), Value::from(0.5)];
+    let loss = losses::cross_entropy(&logits, 1);
+
+    let exps: Vec<f64> = [1.0_f64, 2.0, 0.5].iter().map(|z| z.exp()).collect();
+    let total: f64 = exps.iter().sum();
+    assert!((loss.borrow().data + (exps[1] / total).ln()).abs() < EPS);
+
+    // d/dz_i = softmax_i - [i == target]
+    loss.backward();
+    for (i, z) in logits.iter().enumerate() {
+        let expected = exps[i] / total - if i == 1 { 1.0 } else { 0.0 };
+        assert!((z.borrow().grad - expected).abs() < EPS);
+    }
+}
+
+#[test]
+fn cross_entropy_batch_is_mean_of_samples() {
+    let raw = [vec![1.0, -1.0], vec![0.2, 0.3], vec![3.0, 0.0]];
+    let targets = [0, 1, 1];
+    let logits: Vec<Vec<Value>> = raw
+        .iter()
+        .map(|row| row.iter().map(|&z| Value::from(z)).collect())
+        .collect();
+
+    let batch = losses::cross_entropy_batch(&logits, &targets);
+    let mean = logits
+        .iter()
+        .zip(targets)
+        .map(|(z, t)| losses::cross_entropy(z, t).borrow().data)
+        .sum::<f64>()
+        / 3.0;
+    assert!((batch.borrow().data - mean).abs() < EPS);
+}
+
+#[test]
+fn sum_of_squares_gradient_is_twice_param() {
+    let params = vec![Value::from(1.5), Value::from(-2.0), Value::from(0.25)];
+    let total = losses::sum_of_squares(&params);
+    assert!((total.borrow().data - (2.25 + 4.0 + 0.0625)).abs() < EPS);
+
+    total.backward();
+    for p in &params {
+        assert!((p.borrow().grad - 2.0 * p.borrow().data).abs() < EPS);
+    }
+}
+
+#[test]
+fn triplet_loss_respects_margin() {
+    let vals = |d: &[f64]| d.iter().map(|&x| Value::from(x)).collect::<Vec<Value>>();
+    let anchor = vals(&[0.0, 0.0]);
+    let positive = vals(&[0.3, 0.4]);
+
+    // d(a, p) = 0.5, d(a, n) = 10: satisfied
+    let far = vals(&[6.0, 8.0]);
+    let loss = losses::triplet(&anchor, &positive, &far, 1.0);
+    assert_eq!(loss.borrow().data, 0.0);
+
+    // d(a, n) = 1: 0.5 - 1 + 1 = 0.5
+    let near = vals(&[0.6, 0.8]);
+    let loss = losses::triplet(&anchor, &positive, &near, 1.0);
+    assert!((loss.borrow().data - 0.5).abs() < EPS);
+
+    loss.backward();
+    // Pulled towards the anchor, pushed away from it
+    assert!(positive[0].borrow().grad > 0.0);
+    assert!(near[0].borrow().grad < 0.0);
+}
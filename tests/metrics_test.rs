@@ -0,0 +1,28 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{metrics, Value};
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn balanced_accuracy_differs_on_imbalanced_labels() {
+    // 8 negatives, 2 positives; the classifier gets 7/8 negatives and 1/2 positives
+    let targets = [-1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 1.0, 1.0];
+    let raw = [-0.5, -1.0, -2.0, -0.1, -0.3, -0.9, -0.4, 0.7, 1.2, -0.2];
+    let scores: Vec<Value> = raw.iter().map(|&s| Value::from(s)).collect();
+
+    let plain = raw
+        .iter()
+        .zip(&targets)
+        .filter(|(s, t)| (**s > 0.0) == (**t > 0.0))
+        .count() as f64
+        / targets.len() as f64;
+    let balanced = metrics::balanced_accuracy(&scores, &targets);
+
+    assert!((plain - 0.8).abs() < EPS);
+    assert!((balanced - (7.0 / 8.0 + 0.5) / 2.0).abs() < EPS);
+
+    // Always predicting the majority class is only worth 0.5
+    let majority: Vec<Value> = targets.iter().map(|_| Value::from(-1.0)).collect();
+    assert!((metrics::balanced_accuracy(&majority, &targets) - 0.5).abs() < EPS);
+}
@@ -0,0 +1,425 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{Value, MLP};
+
+const EPS: f64 = 0.0001;
+
+// Unique per process, so concurrent test runs don't overwrite each other's files
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("rustygrad_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn decision_grid_matches_forward() {
+    let model = MLP::new(2, vec![4, 1]);
+    let grid = model.decision_grid(2.0, 10);
+
+    assert_eq!(grid.len(), 10);
+    assert!(grid.iter().all(|row| row.len() == 10));
+
+    // Top-left corner and an interior point
+    for (i, j, x, y) in [(0, 0, -2.0, 2.0), (3, 7, 0.8, 0.8)] {
+        let out = &model.forward(vec![Value::from(x), Value::from(y)])[0];
+        assert!((grid[i][j] - out.borrow().data).abs() < EPS);
+    }
+}
+
+#[test]
+fn weight_and_grad_histograms() {
+    // 2 neurons with 1 weight and 1 bias each
+    let model = MLP::new(1, vec![2]);
+    for (p, v) in model.parameters().iter().zip([0.0, 0.1, 0.95, 1.0]) {
+        p.borrow_mut().data = v;
+        p.borrow_mut().grad = -v;
+    }
+
+    assert_eq!(model.weight_histogram(2), vec![2, 2]);
+    assert_eq!(model.weight_histogram(4), vec![2, 0, 0, 2]);
+    assert_eq!(model.grad_histogram(4), vec![2, 0, 0, 2]);
+
+    model.zero_grad();
+    assert_eq!(model.grad_histogram(3), vec![4, 0, 0]);
+}
+
+#[test]
+fn parameters_iter_matches_parameters() {
+    let model = MLP::new(3, vec![4, 2]);
+    let owned = model.parameters();
+    let borrowed: Vec<&Value> = model.parameters_iter().collect();
+
+    assert_eq!(owned.len(), 4 * (3 + 1) + 2 * (4 + 1));
+    assert_eq!(owned.len(), borrowed.len());
+    assert!(owned.iter().zip(borrowed).all(|(a, b)| a == b));
+}
+
+#[test]
+fn forward_with_activations_records_every_layer() {
+    let model = MLP::new(2, vec![3, 4, 1]);
+    let x = vec![Value::from(1.0), Value::from(-1.0)];
+    let (out, activations) = model.forward_with_activations(x);
+
+    assert_eq!(activations.len(), 3);
+    assert_eq!(activations[0].len(), 3);
+    assert_eq!(activations[1].len(), 4);
+    assert_eq!(activations[2], out);
+
+    // Hidden activations remain part of the graph
+    out[0].backward();
+    let first_hidden_param = &model.parameters()[0];
+    let expected = activations[0][0].borrow().grad;
+    let hidden_active = activations[0][0].borrow().data > 0.0;
+    assert_eq!(
+        first_hidden_param.borrow().grad,
+        if hidden_active { expected } else { 0.0 }
+    );
+}
+
+#[cfg(feature = "profile")]
+#[test]
+fn profile_counts_forward_nodes() {
+    let model = MLP::new(2, vec![3, 1]);
+    let x = vec![Value::from(1.0), Value::from(-1.0)];
+    let out = &model.forward(x)[0];
+
+    // Everything in the graph except the inputs and parameters was built by forward
+    let profile = model.last_profile();
+    assert!(profile.nodes > 0);
+    assert_eq!(
+        profile.nodes,
+        out.graph_size() - 2 - model.parameters().len()
+    );
+
+    model.backward(out);
+    assert!(model.last_profile().backward_time > std::time::Duration::ZERO);
+    assert_eq!(model.last_profile().nodes, profile.nodes);
+}
+
+#[test]
+fn load_pytorch_json_fixture() {
+    let model = MLP::load_pytorch_json("tests/data/pytorch_mlp.json").unwrap();
+    assert_eq!(
+        format!("{:?}", model),
+        "MLP { layers: [Layer { neurons: [ReLU(2), ReLU(2)] }, Layer { neurons: [Linear(2)] }] }"
+    );
+
+    // hidden = relu([1 - 4 + 0.1, 1.5 - 0.2]) = [0, 1.3]; out = 2 * 0 - 1.3 + 0.3
+    let out = &model.forward(vec![Value::from(1.0), Value::from(2.0)])[0];
+    assert!((-1.0 - out.borrow().data).abs() < EPS);
+}
+
+#[test]
+fn load_pytorch_json_rejects_bad_shapes() {
+    let path = temp_path("bad_shapes.json");
+    std::fs::write(
+        &path,
+        r#"[{"weight": [[1.0, 2.0]], "bias": [0.0]}, {"weight": [[1.0, 2.0, 3.0]], "bias": [0.0]}]"#,
+    )
+    .unwrap();
+    assert!(MLP::load_pytorch_json(path.to_str().unwrap()).is_err());
+    assert!(MLP::load_pytorch_json("tests/data/missing.json").is_err());
+}
+
+#[test]
+fn json_save_and_load_round_trip() {
+    use rustygrad::MlpBuilder;
+
+    let model = MlpBuilder::new(2).linear(3).relu(2).linear(1).build();
+    let path = temp_path("round_trip.json");
+    let path = path.to_str().unwrap();
+    model.save_json(path).unwrap();
+
+    let restored = MLP::load(path).unwrap();
+    assert_eq!(format!("{:?}", restored), format!("{:?}", model));
+    for (a, b) in model.parameters_iter().zip(restored.parameters_iter()) {
+        assert_eq!(a.borrow().data, b.borrow().data);
+    }
+}
+
+#[test]
+fn predict_class_matches_sign_of_forward() {
+    use rustygrad::FitOptions;
+
+    let xs: Vec<Vec<f64>> = (0..16)
+        .map(|i| vec![(i as f64 - 7.5) / 4.0, ((i * 7) % 5) as f64 / 5.0])
+        .collect();
+    let ys: Vec<f64> = xs.iter().map(|x| x[0].signum()).collect();
+    let model = MLP::new(2, vec![4, 1]);
+    model
+        .fit(
+            &xs,
+            &ys,
+            FitOptions {
+                epochs: 10,
+                ..FitOptions::default()
+            },
+        )
+        .unwrap();
+
+    for x in &xs {
+        let out = &model.forward(x.iter().map(|&xi| Value::from(xi)).collect())[0];
+        let expected = if out.borrow().data > 0.0 { 1.0 } else { -1.0 };
+        assert_eq!(model.predict_class(x), expected);
+        assert!((model.predict(x)[0] - out.borrow().data).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn cached_parameters_alias_layer_values() {
+    let model = MLP::new(1, vec![1]);
+    let cached = model.parameters();
+
+    // Writing through the cache changes what the layers compute: [bias, weight]
+    cached[0].borrow_mut().data = 0.5;
+    cached[1].borrow_mut().data = 2.0;
+    assert_eq!(model.predict(&[3.0]), vec![6.5]);
+
+    // Gradients accumulated on the layers' values show up in the cache
+    let out = &model.forward(vec![Value::from(3.0)])[0];
+    out.backward();
+    assert_eq!(cached[0].borrow().grad, 1.0);
+    assert_eq!(cached[1].borrow().grad, 3.0);
+
+    model.zero_grad();
+    assert!(model.parameters_iter().all(|p| p.borrow().grad == 0.0));
+}
+
+#[test]
+fn forward_f64_matches_manual_lifting() {
+    let model = MLP::new(3, vec![4, 2]);
+    let x = [0.5, -1.0, 2.0];
+
+    let lifted = model.forward_f64(&x);
+    let manual = model.forward(x.iter().map(|&xi| Value::from(xi)).collect());
+    for (a, b) in lifted.iter().zip(&manual) {
+        assert_eq!(a.borrow().data, b.borrow().data);
+    }
+
+    lifted[0].backward();
+    assert!(model.parameters_iter().any(|p| p.borrow().grad != 0.0));
+}
+
+#[test]
+fn from_arch_str_builds_shapes() {
+    let model = MLP::from_arch_str("2-16-16-1").unwrap();
+    assert_eq!(model.forward_f64(&[0.5, -0.5]).len(), 1);
+    // (2 + 1) * 16 + (16 + 1) * 16 + (16 + 1) * 1
+    assert_eq!(model.parameters().len(), 337);
+
+    let model = MLP::try_from("3-4").unwrap();
+    assert_eq!(model.predict(&[1.0, 2.0, 3.0]).len(), 4);
+}
+
+#[test]
+fn from_arch_str_rejects_malformed() {
+    for bad in ["", "2", "2-", "-2-1", "2-x-1", "2-0-1", "2--1", "2-16-1.5"] {
+        assert!(MLP::from_arch_str(bad).is_err(), "accepted {:?}", bad);
+    }
+}
+
+#[test]
+fn average_takes_parameter_midpoints() {
+    let a = MLP::new(2, vec![3, 1]);
+    let b = MLP::new(2, vec![3, 1]);
+    let soup = MLP::average(&[&a, &b]).unwrap();
+
+    assert_eq!(format!("{:?}", soup), format!("{:?}", a));
+    for ((s, pa), pb) in soup
+        .parameters_iter()
+        .zip(a.parameters_iter())
+        .zip(b.parameters_iter())
+    {
+        let mid = (pa.borrow().data + pb.borrow().data) / 2.0;
+        assert!((s.borrow().data - mid).abs() < EPS);
+    }
+
+    // The soup owns fresh parameters
+    soup.parameters()[1].borrow_mut().data = 100.0;
+    assert_ne!(a.parameters()[1].borrow().data, 100.0);
+}
+
+#[test]
+fn average_rejects_mismatched_architectures() {
+    let a = MLP::new(2, vec![3, 1]);
+    assert!(MLP::average(&[&a, &MLP::new(2, vec![4, 1])]).is_err());
+    assert!(MLP::average(&[&a, &MLP::new(3, vec![3, 1])]).is_err());
+    assert!(MLP::average(&[&a, &MLP::new(2, vec![3])]).is_err());
+    assert!(MLP::average(&[]).is_err());
+}
+
+#[test]
+fn index_returns_layers() {
+    let model = MLP::new(2, vec![4, 3, 1]);
+    assert_eq!(model.num_layers(), 3);
+    assert_eq!(model[0].nout(), 4);
+    assert_eq!(model[1].nout(), 3);
+    assert_eq!(model.layer(2).unwrap().nout(), 1);
+    assert!(model.layer(3).is_none());
+}
+
+#[test]
+fn forward_padded_fits_input_size() {
+    let model = MLP::new(4, vec![3, 2]);
+    assert_eq!(model.nin(), 4);
+
+    let (short, adjusted) = model.forward_padded(&[1.0, 2.0], 0.0);
+    assert!(adjusted);
+    let expected = model.predict(&[1.0, 2.0, 0.0, 0.0]);
+    assert_eq!(short.len(), 2);
+    for (out, e) in short.iter().zip(&expected) {
+        assert!((out.borrow().data - e).abs() < EPS);
+    }
+
+    let (long, adjusted) = model.forward_padded(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], -1.0);
+    assert!(adjusted);
+    let expected = model.predict(&[1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(long.len(), 2);
+    for (out, e) in long.iter().zip(&expected) {
+        assert!((out.borrow().data - e).abs() < EPS);
+    }
+
+    let (_, adjusted) = model.forward_padded(&[1.0, 2.0, 3.0, 4.0], 0.0);
+    assert!(!adjusted);
+}
+
+#[test]
+fn forward_padded_on_a_model_without_layers() {
+    use rustygrad::MlpBuilder;
+
+    let model = MlpBuilder::new(3).build();
+    assert_eq!(model.nin(), 0);
+    let (out, adjusted) = model.forward_padded(&[1.0, 2.0], 0.0);
+    assert!(out.is_empty());
+    assert!(adjusted);
+}
+
+#[test]
+fn weight_parameters_exclude_biases() {
+    let model = MLP::new(3, vec![4, 2]);
+    let weights = model.weight_parameters();
+    assert_eq!(weights.len(), model.parameters().len() - (4 + 2));
+    assert_eq!(weights.len(), 3 * 4 + 4 * 2);
+    // Aliases of the model's own nodes, and the first neuron's bias is not among them
+    let params = model.parameters();
+    assert!(weights.iter().all(|w| params.contains(w)));
+    assert!(!weights.contains(&params[0]));
+}
+
+#[test]
+fn save_and_load_round_trip() {
+    use rustygrad::{Activation, MlpBuilder};
+
+    let model = MlpBuilder::new(3)
+        .relu(4)
+        .layer(2, Activation::Tanh)
+        .linear(1)
+        .build();
+    let path = temp_path("round_trip.bin");
+    let path = path.to_str().unwrap();
+    model.save(path).unwrap();
+
+    for restored in [MLP::load_binary(path).unwrap(), MLP::load(path).unwrap()] {
+        assert_eq!(format!("{:?}", restored), format!("{:?}", model));
+        for (a, b) in model.parameters_iter().zip(restored.parameters_iter()) {
+            assert_eq!(a.borrow().data, b.borrow().data);
+        }
+    }
+}
+
+#[test]
+fn binary_load_rejects_bad_files() {
+    let model = MLP::new(2, vec![3, 1]);
+    let good = temp_path("good.bin");
+    model.save(good.to_str().unwrap()).unwrap();
+    let bytes = std::fs::read(&good).unwrap();
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[4] = 99;
+    let mut bad_activation = bytes.clone();
+    bad_activation[12 + 8] = 42;
+    // One layer with nin = nout = u32::MAX overflows the parameter count
+    let overflowing = [
+        b"RGRD".as_slice(),
+        &1u32.to_le_bytes(),
+        &1u32.to_le_bytes(),
+        &u32::MAX.to_le_bytes(),
+        &u32::MAX.to_le_bytes(),
+        &[0],
+    ]
+    .concat();
+    let mut huge_layer_count = bytes.clone();
+    huge_layer_count[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+    let cases = [
+        bytes[..bytes.len() - 3].to_vec(),
+        bytes[..10].to_vec(),
+        [bytes.clone(), vec![0; 8]].concat(),
+        b"garbage that is not a model".to_vec(),
+        wrong_version,
+        bad_activation,
+        overflowing,
+        huge_layer_count,
+        vec![],
+    ];
+    let bad = temp_path("bad.bin");
+    for case in cases {
+        std::fs::write(&bad, case).unwrap();
+        assert!(MLP::load_binary(bad.to_str().unwrap()).is_err());
+    }
+}
+
+#[test]
+fn input_gradient_of_linear_model_is_weight_row() {
+    use rustygrad::MlpBuilder;
+
+    let model = MlpBuilder::new(3).linear(2).linear(1).build();
+    let first = model[0].weights();
+    let second = model[1].weights();
+    // Two stacked linear layers act as the single row W2 · W1
+    let effective: Vec<f64> = (0..3)
+        .map(|j| (0..2).map(|k| second[0][k] * first[k][j]).sum())
+        .collect();
+
+    model.parameters()[0].borrow_mut().grad = 0.25;
+    let grad = model.input_gradient(&[0.4, -1.0, 2.0]);
+    for (g, e) in grad.iter().zip(&effective) {
+        assert!((g - e).abs() < EPS);
+    }
+    assert_eq!(model.parameters()[0].borrow().grad, 0.25);
+}
+
+#[test]
+fn seeded_construction_is_reproducible() {
+    use rustygrad::{seeded_rng, Activation, Layer, MlpBuilder};
+
+    let weights =
+        |m: &MLP| -> Vec<f64> { m.parameters().iter().map(|p| p.borrow().data).collect() };
+    assert_eq!(
+        weights(&MLP::seeded(3, vec![4, 2], 11)),
+        weights(&MLP::seeded(3, vec![4, 2], 11))
+    );
+    assert_ne!(
+        weights(&MLP::seeded(3, vec![4, 2], 11)),
+        weights(&MLP::seeded(3, vec![4, 2], 12))
+    );
+
+    let built = |seed| MlpBuilder::seeded(3, seed).relu(4).linear(2).build();
+    assert_eq!(weights(&built(5)), weights(&built(5)));
+
+    // One generator threaded through several components is consumed in order
+    let mut a = seeded_rng(9);
+    let mut b = seeded_rng(9);
+    let la = Layer::with_rng(2, 3, Activation::Tanh, &mut a);
+    let lb = Layer::with_rng(2, 3, Activation::Tanh, &mut b);
+    assert_eq!(la.weights(), lb.weights());
+    let x = vec![Value::from(1.0), Value::from(-0.5)];
+    let ya: Vec<f64> = la
+        .forward_train_with_rng(&x, 0.5, &mut a)
+        .iter()
+        .map(|v| v.borrow().data)
+        .collect();
+    let yb: Vec<f64> = lb
+        .forward_train_with_rng(&x, 0.5, &mut b)
+        .iter()
+        .map(|v| v.borrow().data)
+        .collect();
+    assert_eq!(ya, yb);
+}
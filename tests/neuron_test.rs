@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use rustygrad::Neuron;
 use rustygrad::Value;
 
@@ -15,3 +17,77 @@ fn neuron_example() {
         );
     }
 }
+
+#[test]
+fn forward_with_preact_returns_both_stages() {
+    for _ in 1..20 {
+        let x = vec![Value::from(1.0), Value::from(-2.0)];
+        let n = Neuron::from(2);
+
+        let (preact, out) = n.forward_with_preact(&x);
+        assert_eq!(out.borrow().data, preact.borrow().data.max(0.0));
+
+        // The pre-activation stays connected to the parameters
+        out.backward();
+        let expected = if preact.borrow().data > 0.0 { 1.0 } else { 0.0 };
+        assert_eq!(preact.borrow().grad, expected);
+        assert_eq!(n.parameters()[0].borrow().grad, expected);
+    }
+}
+
+#[test]
+fn tanh_neuron_squashes_output() {
+    use rustygrad::Activation;
+
+    let n = Neuron::with_activation(2, Activation::Tanh);
+    assert_eq!(format!("{:?}", n), "Tanh(2)");
+    for scale in [-5.0, -1.0, 0.5, 5.0] {
+        let x = vec![Value::from(scale), Value::from(-scale)];
+        let y = n.forward(&x).borrow().data;
+        assert!(y > -1.0 && y < 1.0);
+    }
+}
+
+#[test]
+fn wide_neuron_sums_accurately() {
+    let n = Neuron::new(1000, false);
+    let params = n.parameters();
+    for w in &params[1..] {
+        w.borrow_mut().data = 0.1;
+    }
+    let x: Vec<Value> = (0..1000).map(|_| Value::from(1.0)).collect();
+
+    // The exact sum rounds to 100.0; a left-to-right sum is off by ~1.4e-12
+    let out = n.forward(&x);
+    assert!((out.borrow().data - 100.0).abs() < 1e-13);
+
+    out.backward();
+    assert!(params[1..].iter().all(|w| w.borrow().grad == 1.0));
+    assert!(x.iter().all(|xi| xi.borrow().grad == 0.1));
+    assert_eq!(params[0].borrow().grad, 1.0);
+}
+
+#[test]
+fn activation_apply_matches_value_methods() {
+    use rustygrad::Activation;
+
+    fn check(activation: Activation, direct: fn(&Value) -> Value) {
+        for x in [-1.5, -0.2, 0.0, 0.7, 2.0] {
+            let a = Value::from(x);
+            let out = activation.apply(&a);
+            out.backward();
+
+            let b = Value::from(x);
+            let expected = direct(&b);
+            expected.backward();
+
+            assert_eq!(out.borrow().data, expected.borrow().data);
+            assert_eq!(a.borrow().grad, b.borrow().grad);
+        }
+    }
+
+    check(Activation::Linear, |v| v.clone());
+    check(Activation::ReLU, |v| v.relu());
+    check(Activation::Tanh, |v| v.tanh());
+    check(Activation::Sigmoid, |v| v.sigmoid());
+}
@@ -0,0 +1,31 @@
+// Runs under `cargo test --no-default-features`, where the std-only test files
+// compile to nothing.
+#![cfg(not(feature = "std"))]
+
+use rustygrad::{Activation, Value};
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn engine_works_without_std() {
+    let a = Value::from(-4.0);
+    let b = Value::from(2.0);
+    let c = &a + &b;
+    let d = &a * &b + b.pow(3.0);
+    let e = (&c - &d).relu() + Activation::Tanh.apply(&(&c * &d));
+    let f = e.exp().log() + (&c / 4.0).sigmoid();
+    f.backward();
+
+    let expected = (-2.0f64 * 0.0).tanh() + 1.0 / (1.0 + 0.5f64.exp());
+    assert!((f.borrow().data - expected).abs() < EPS);
+    assert!((a.borrow().grad - -3.94125).abs() < EPS, "{:?}", a);
+    assert!((b.borrow().grad - -15.94125).abs() < EPS, "{:?}", b);
+
+    // Graph walks use the alloc collections
+    assert!(f.validate().is_ok());
+    assert_eq!(f.graph_size(), f.clone_graph().graph_size());
+    a.set_leaf(-3.0);
+    f.recompute().unwrap();
+    assert_eq!(a.borrow().grad, 0.0);
+    assert_ne!(a, b);
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "profile")]
+
+use rustygrad::{ops, Value};
+
+#[test]
+fn op_counts_track_known_expression() {
+    ops::reset();
+    let a = Value::from(2.0);
+    let b = Value::from(-3.0);
+    // a*b + a*a, relu, then a - b (= a + (-1 × b))
+    let _ = (&a * &b + &a * &a).relu();
+    let _ = &a - &b;
+
+    let counts = ops::op_counts();
+    assert_eq!(counts["×"], 3);
+    assert_eq!(counts["+"], 2);
+    assert_eq!(counts["ReLU"], 1);
+    assert_eq!(counts.len(), 3);
+
+    ops::reset();
+    assert!(ops::op_counts().is_empty());
+}
@@ -0,0 +1,277 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{Layer, Optimizer, Value, SGD};
+
+#[test]
+fn frozen_layer_is_skipped_by_step() {
+    let frozen = Layer::new(2, 2, true);
+    let trained = Layer::new(2, 2, false);
+    frozen.freeze();
+
+    let before_frozen: Vec<f64> = frozen
+        .parameters()
+        .iter()
+        .map(|p| p.borrow().data)
+        .collect();
+    let before_trained: Vec<f64> = trained
+        .parameters()
+        .iter()
+        .map(|p| p.borrow().data)
+        .collect();
+
+    let params: Vec<Value> = frozen
+        .parameters()
+        .into_iter()
+        .chain(trained.parameters())
+        .collect();
+    for p in &params {
+        p.borrow_mut().grad = 1.0;
+    }
+    let mut sgd = SGD::new(params, 0.5);
+    sgd.step();
+
+    let after_frozen: Vec<f64> = frozen
+        .parameters()
+        .iter()
+        .map(|p| p.borrow().data)
+        .collect();
+    let after_trained: Vec<f64> = trained
+        .parameters()
+        .iter()
+        .map(|p| p.borrow().data)
+        .collect();
+    assert_eq!(before_frozen, after_frozen);
+    for (b, a) in before_trained.iter().zip(&after_trained) {
+        assert_eq!(b - 0.5, *a);
+    }
+
+    // Unfreezing makes the next step apply again
+    frozen.unfreeze();
+    sgd.step();
+    let unfrozen: Vec<f64> = frozen
+        .parameters()
+        .iter()
+        .map(|p| p.borrow().data)
+        .collect();
+    assert!(before_frozen
+        .iter()
+        .zip(&unfrozen)
+        .all(|(b, a)| b - 0.5 == *a));
+}
+
+#[test]
+fn grad_noise_is_seeded_and_scaled() {
+    use rustygrad::add_grad_noise;
+
+    let params: Vec<Value> = (0..10_000).map(|_| Value::from(0.0)).collect();
+    for p in &params {
+        p.borrow_mut().grad = 1.0;
+    }
+
+    // Zero stddev leaves the grads untouched
+    add_grad_noise(&params, 0.0, 3);
+    assert!(params.iter().all(|p| p.borrow().grad == 1.0));
+
+    add_grad_noise(&params, 0.5, 3);
+    let noise: Vec<f64> = params.iter().map(|p| p.borrow().grad - 1.0).collect();
+    let n = noise.len() as f64;
+    let mean = noise.iter().sum::<f64>() / n;
+    let std = (noise.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / n).sqrt();
+    assert!(mean.abs() < 0.02);
+    assert!((std - 0.5).abs() < 0.02);
+
+    // The same seed reproduces the same perturbation
+    let others: Vec<Value> = (0..10_000).map(|_| Value::from(0.0)).collect();
+    add_grad_noise(&others, 0.5, 3);
+    for (p, o) in params.iter().zip(&others) {
+        assert!((p.borrow().grad - 1.0 - o.borrow().grad).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn parameter_groups_use_their_own_lr() {
+    let a = Layer::new(2, 1, false);
+    let b = Layer::new(2, 1, false);
+    for p in a.parameters_iter().chain(b.parameters_iter()) {
+        p.borrow_mut().data = 1.0;
+        p.borrow_mut().grad = 2.0;
+    }
+
+    let mut sgd = SGD::with_groups(vec![(a.parameters(), 0.1), (b.parameters(), 0.01)]);
+    sgd.step();
+
+    assert!(a
+        .parameters_iter()
+        .all(|p| (p.borrow().data - 0.8).abs() < 1e-12));
+    assert!(b
+        .parameters_iter()
+        .all(|p| (p.borrow().data - 0.98).abs() < 1e-12));
+}
+
+#[test]
+fn lerp_data_into_interpolates() {
+    use rustygrad::{lerp_data_into, MLP};
+
+    let target = MLP::new(2, vec![3, 1]);
+    let source = MLP::new(2, vec![3, 1]);
+    let data = |m: &MLP| -> Vec<f64> { m.parameters_iter().map(|p| p.borrow().data).collect() };
+    let (t0, s0) = (data(&target), data(&source));
+
+    lerp_data_into(&target.parameters(), &source.parameters(), 0.0);
+    assert_eq!(data(&target), t0);
+
+    lerp_data_into(&target.parameters(), &source.parameters(), 0.5);
+    for ((t, a), b) in data(&target).iter().zip(&t0).zip(&s0) {
+        assert!((t - (a + b) / 2.0).abs() < 1e-12);
+    }
+
+    lerp_data_into(&target.parameters(), &source.parameters(), 1.0);
+    assert_eq!(data(&target), s0);
+    assert_eq!(data(&source), s0);
+}
+
+#[test]
+fn swa_averages_snapshots() {
+    use rustygrad::{Swa, MLP};
+
+    let model = MLP::new(2, vec![2, 1]);
+    let mut swa = Swa::new();
+
+    let first: Vec<f64> = model.parameters_iter().map(|p| p.borrow().data).collect();
+    swa.update(&model);
+    for p in model.parameters_iter() {
+        p.borrow_mut().data += 1.0;
+    }
+    swa.update(&model);
+
+    swa.finalize(&model);
+    for (p, f) in model.parameters_iter().zip(&first) {
+        assert!((p.borrow().data - (f + 0.5)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn adagrad_decreases_quadratic_with_shrinking_steps() {
+    use rustygrad::Adagrad;
+
+    let x = Value::from(3.0);
+    let mut opt = Adagrad::new(vec![x.clone()], 0.5, 1e-8);
+
+    let mut losses = vec![];
+    let mut steps = vec![];
+    for _ in 0..20 {
+        let loss = x.pow(2.0);
+        x.borrow_mut().grad = 0.0;
+        loss.backward();
+        losses.push(loss.borrow().data);
+
+        let before = x.borrow().data;
+        opt.step();
+        steps.push((x.borrow().data - before).abs());
+    }
+
+    assert!(losses.windows(2).all(|w| w[1] < w[0]));
+    assert!(steps.windows(2).all(|w| w[1] < w[0]));
+}
+
+fn train_step(model: &rustygrad::MLP, opt: &mut dyn Optimizer) {
+    let out = &model.forward_f64(&[0.3, -0.7])[0];
+    let loss = (out - Value::from(1.5)).pow(2.0);
+    model.zero_grad();
+    loss.backward();
+    opt.step();
+}
+
+// Trains a few steps, checkpoints weights and optimizer state, restores both
+// into fresh objects and checks the next step matches uninterrupted training
+macro_rules! assert_resumes_exactly {
+    ($name:literal, $make:expr) => {{
+        use rustygrad::MLP;
+
+        let dir = std::env::temp_dir();
+        let weights_path = dir.join(concat!("rustygrad_resume_weights_", $name, ".json"));
+        let state_path = dir.join(concat!("rustygrad_resume_state_", $name, ".json"));
+        let weights_path = weights_path.to_str().unwrap();
+        let state_path = state_path.to_str().unwrap();
+
+        let model = MLP::new(2, vec![3, 1]);
+        let mut opt = $make(model.parameters());
+        for _ in 0..3 {
+            train_step(&model, &mut opt);
+        }
+        model.save(weights_path).unwrap();
+        opt.save_state(state_path).unwrap();
+
+        let resumed = MLP::load(weights_path).unwrap();
+        let mut resumed_opt = $make(resumed.parameters());
+        resumed_opt.load_state(state_path).unwrap();
+
+        train_step(&model, &mut opt);
+        train_step(&resumed, &mut resumed_opt);
+        for (a, b) in model.parameters_iter().zip(resumed.parameters_iter()) {
+            assert_eq!(a.borrow().data, b.borrow().data);
+        }
+    }};
+}
+
+#[test]
+fn optimizer_state_round_trip_resumes_exactly() {
+    use rustygrad::{Adagrad, Adam, RMSProp};
+
+    assert_resumes_exactly!("adam", |p| Adam::new(p, 0.01));
+    assert_resumes_exactly!("rmsprop", |p| RMSProp::new(p, 0.01));
+    assert_resumes_exactly!("momentum", |p| SGD::with_momentum(p, 0.01, 0.9));
+    assert_resumes_exactly!("adagrad", |p| Adagrad::new(p, 0.1, 1e-8));
+}
+
+#[test]
+fn load_state_rejects_mismatched_parameter_count() {
+    use rustygrad::Adam;
+
+    let path = std::env::temp_dir().join("rustygrad_state_mismatch.json");
+    let path = path.to_str().unwrap();
+    Adam::new(vec![Value::from(1.0)], 0.1)
+        .save_state(path)
+        .unwrap();
+
+    let mut adam = Adam::new(vec![Value::from(1.0), Value::from(2.0)], 0.1);
+    assert!(adam.load_state(path).is_err());
+}
+
+#[test]
+fn failed_load_state_leaves_the_optimizer_untouched() {
+    use rustygrad::Adam;
+
+    let step = |x: &Value, opt: &mut Adam| {
+        let grad = 2.0 * x.borrow().data;
+        x.borrow_mut().grad = grad;
+        opt.step();
+    };
+    let (a, b) = (Value::from(1.0), Value::from(1.0));
+    let mut reference = Adam::new(vec![a.clone()], 0.1);
+    let mut adam = Adam::new(vec![b.clone()], 0.1);
+    for _ in 0..2 {
+        step(&a, &mut reference);
+        step(&b, &mut adam);
+    }
+
+    // A good "m" followed by a bad "v"
+    let path = std::env::temp_dir().join("rustygrad_state_bad_v.json");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "{\"step\": 7, \"m\": [100.0], \"v\": [\"x\"]}").unwrap();
+    assert!(adam.load_state(path).is_err());
+
+    step(&a, &mut reference);
+    step(&b, &mut adam);
+    assert_eq!(a.borrow().data, b.borrow().data);
+}
+
+#[test]
+#[should_panic(expected = "architecture changed")]
+fn swa_finalize_rejects_a_different_architecture() {
+    use rustygrad::{Swa, MLP};
+
+    let mut swa = Swa::new();
+    swa.update(&MLP::new(2, vec![2, 1]));
+    swa.finalize(&MLP::new(2, vec![3, 1]));
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{parse::parse_expr, Value};
+use std::collections::HashMap;
+
+const EPS: f64 = 0.0001;
+
+fn vars(values: &[(&str, f64)]) -> HashMap<String, Value> {
+    values
+        .iter()
+        .map(|&(name, x)| (name.to_string(), Value::from(x)))
+        .collect()
+}
+
+#[test]
+fn parse_expr_matches_hand_built_graph() {
+    let parsed_vars = vars(&[("a", 1.5), ("b", -4.0), ("c", 2.0)]);
+    let parsed = parse_expr("(a+b)*c", &parsed_vars).unwrap();
+    parsed.backward();
+
+    let (a, b, c) = (Value::from(1.5), Value::from(-4.0), Value::from(2.0));
+    let built = (&a + &b) * &c;
+    built.backward();
+
+    assert!((parsed.borrow().data - built.borrow().data).abs() < EPS);
+    for (name, v) in [("a", &a), ("b", &b), ("c", &c)] {
+        assert!((parsed_vars[name].borrow().grad - v.borrow().grad).abs() < EPS);
+    }
+}
+
+#[test]
+fn parse_expr_precedence_and_functions() {
+    let v = vars(&[("x", 0.5), ("y", 3.0)]);
+    let eval = |s: &str| parse_expr(s, &v).unwrap().borrow().data;
+
+    assert!((eval("1 + 2 * 3") - 7.0).abs() < EPS);
+    assert!((eval("(2 ^ 3) ^ 2") - 64.0).abs() < EPS);
+    assert!((eval("-y^2") - -9.0).abs() < EPS);
+    assert!((eval("y - x - 1") - 1.5).abs() < EPS);
+    assert!((eval("y / 2 / 3") - 0.5).abs() < EPS);
+    assert!((eval("x^-1") - 2.0).abs() < EPS);
+    assert!((eval("2^3^2") - 512.0).abs() < EPS);
+    assert!((eval("y^(1+1)") - 9.0).abs() < EPS);
+    assert!((eval("2^-(1+1)") - 0.25).abs() < EPS);
+    assert!((eval("relu(x - y) + tanh(x)") - 0.5f64.tanh()).abs() < EPS);
+}
+
+#[test]
+fn parse_expr_rejects_unknown_symbols() {
+    let v = vars(&[("x", 1.0)]);
+    for s in [
+        "x + z", "sin(x)", "x % 2", "(x + 1", "x +", "x ^ x", "2^(x+1)", "x x", "",
+    ] {
+        assert!(parse_expr(s, &v).is_err(), "{:?} should not parse", s);
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{MinMaxScaler, StandardScaler};
+
+const EPS: f64 = 1e-12;
+
+fn data() -> Vec<Vec<f64>> {
+    vec![
+        vec![1.0, -5.0, 3.0],
+        vec![4.0, 5.0, 3.0],
+        vec![2.5, 0.0, 3.0],
+        vec![-2.0, 1.0, 3.0],
+    ]
+}
+
+#[test]
+fn min_max_spans_feature_range() {
+    let xs = data();
+    let scaler = MinMaxScaler::fit_range(&xs, (-1.0, 2.0));
+    let scaled = scaler.transform(&xs);
+
+    for j in 0..2 {
+        let col: Vec<f64> = scaled.iter().map(|x| x[j]).collect();
+        let lo = col.iter().cloned().fold(f64::INFINITY, f64::min);
+        let hi = col.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        assert!((lo + 1.0).abs() < EPS);
+        assert!((hi - 2.0).abs() < EPS);
+    }
+    // The constant column collapses onto the lower bound instead of NaN
+    assert!(scaled.iter().all(|x| x[2] == -1.0));
+
+    let restored = scaler.inverse_transform(&scaled);
+    for (r, x) in restored.iter().flatten().zip(xs.iter().flatten()) {
+        assert!((r - x).abs() < 1e-9);
+    }
+    assert_eq!(MinMaxScaler::fit(&xs).transform(&xs)[1][0], 1.0);
+}
+
+#[test]
+fn standard_scaler_round_trip() {
+    let xs = data();
+    let scaler = StandardScaler::fit(&xs);
+    let scaled = scaler.transform(&xs);
+
+    let mean: f64 = scaled.iter().map(|x| x[0]).sum::<f64>() / 4.0;
+    assert!(mean.abs() < EPS);
+    assert!(scaled.iter().all(|x| x[2] == 0.0));
+
+    let restored = scaler.inverse_transform(&scaled);
+    for (r, x) in restored.iter().flatten().zip(xs.iter().flatten()) {
+        assert!((r - x).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{LearningRate, Scheduler, Warmup};
+
+const EPS: f64 = 1e-12;
+
+#[test]
+fn warmup_ramps_then_delegates() {
+    let inner = LearningRate::Linear {
+        start: 1.0,
+        end: 0.1,
+        steps: 10,
+    };
+    let schedule = Warmup {
+        inner,
+        warmup_steps: 4,
+    };
+
+    assert!(schedule.lr(0).abs() < EPS);
+    for step in 0..4 {
+        assert!((schedule.lr(step) - 0.25 * step as f64).abs() < EPS);
+    }
+    for step in 4..20 {
+        assert!((schedule.lr(step) - inner.lr(step - 4)).abs() < EPS);
+    }
+    assert!((schedule.lr(100) - 0.1).abs() < EPS);
+}
@@ -0,0 +1,232 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{read_csv_file, train_mlp_from_csv, Value};
+
+#[test]
+fn train_from_csv_fits_tiny_dataset() {
+    let path = "tests/data/tiny.csv";
+    let (model, scaler) = train_mlp_from_csv(path, vec![8, 1], 50, 0.1).unwrap();
+
+    let points = read_csv_file(path).unwrap();
+    let xs: Vec<Vec<f64>> = points.iter().map(|p| vec![p.x, p.y]).collect();
+    let xs = scaler.transform(&xs);
+
+    for (x, p) in xs.iter().zip(&points) {
+        let score = &model.forward(x.iter().map(|&xi| Value::from(xi)).collect())[0];
+        assert_eq!(score.borrow().data > 0.0, p.label > 0.0);
+    }
+}
+
+#[test]
+fn train_from_csv_reports_missing_file() {
+    assert!(train_mlp_from_csv("tests/data/missing.csv", vec![1], 1, 0.1).is_err());
+}
+
+#[test]
+fn fit_reduces_loss_on_separable_data() {
+    use rustygrad::{FitOptions, LearningRate, Loss, MLP};
+
+    // Inputs are small enough that no initial weights can satisfy the margin
+    let xs: Vec<Vec<f64>> = (0..20)
+        .map(|i| {
+            let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+            vec![side * (0.2 + i as f64 * 0.01), side * 0.1]
+        })
+        .collect();
+    let ys: Vec<f64> = xs.iter().map(|x| x[0].signum()).collect();
+
+    let model = MLP::new(2, vec![1]);
+    let opts = FitOptions {
+        epochs: 20,
+        batch_size: 5,
+        learning_rate: Box::new(LearningRate::Linear {
+            start: 0.1,
+            end: 0.01,
+            steps: 20,
+        }),
+        loss: Loss::Hinge,
+        shuffle_seed: Some(7),
+        checkpoint_every: None,
+        on_epoch: None,
+    };
+    let history = model.fit(&xs, &ys, opts).unwrap();
+
+    assert_eq!(history.len(), 20);
+    assert!(history[19] < history[0]);
+}
+
+#[test]
+fn fit_writes_periodic_checkpoints() {
+    use rustygrad::{FitOptions, MLP};
+
+    let dir = std::env::temp_dir().join(format!("rustygrad_ckpt_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let template = dir.join("model_{epoch}.json");
+
+    let model = MLP::new(2, vec![3, 1]);
+    let xs = vec![vec![1.0, 0.0], vec![-1.0, 0.0]];
+    let ys = vec![1.0, -1.0];
+    let opts = FitOptions {
+        epochs: 5,
+        checkpoint_every: Some((2, template.to_str().unwrap().to_string())),
+        ..FitOptions::default()
+    };
+    model.fit(&xs, &ys, opts).unwrap();
+
+    let mut files: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["model_2.json", "model_4.json"]);
+
+    // The latest checkpoint can be loaded back
+    let restored = MLP::load(dir.join("model_4.json").to_str().unwrap()).unwrap();
+    assert_eq!(format!("{:?}", restored), format!("{:?}", model));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn fit_returns_checkpoint_write_errors() {
+    use rustygrad::{FitOptions, MLP};
+
+    let model = MLP::new(2, vec![1]);
+    let opts = FitOptions {
+        epochs: 3,
+        checkpoint_every: Some((1, "/nonexistent/dir/model.json".to_string())),
+        ..FitOptions::default()
+    };
+    let err = model.fit(&[vec![1.0, 0.0]], &[1.0], opts).unwrap_err();
+    assert!(err.to_string().contains("/nonexistent/dir/model.json"));
+}
+
+#[test]
+fn train_streaming_reduces_loss() {
+    use rustygrad::{train_streaming, FitOptions, Loss, MLP};
+
+    // Small inputs, so the hinge loss starts positive for any initial weights
+    let model = MLP::new(2, vec![1]);
+    let opts = FitOptions {
+        epochs: 10,
+        batch_size: 4,
+        loss: Loss::Hinge,
+        ..FitOptions::default()
+    };
+    let history = train_streaming("tests/data/stream.csv", &model, opts).unwrap();
+
+    // 20 rows in batches of 4 over 10 passes
+    assert_eq!(history.len(), 50);
+    let first: f64 = history[..5].iter().sum();
+    let last: f64 = history[45..].iter().sum();
+    assert!(last < first, "loss went from {} to {}", first, last);
+
+    assert!(train_streaming("tests/data/missing.csv", &model, FitOptions::default()).is_err());
+}
+
+#[test]
+fn fit_calls_on_epoch_every_epoch() {
+    use rustygrad::{FitOptions, MLP};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let xs = vec![
+        vec![1.0, 0.5],
+        vec![-1.0, -0.5],
+        vec![0.8, 0.2],
+        vec![-0.6, 0.1],
+    ];
+    let ys = vec![1.0, -1.0, 1.0, -1.0];
+    let seen = Rc::new(RefCell::new(vec![]));
+    let log = Rc::clone(&seen);
+
+    let model = MLP::new(2, vec![3, 1]);
+    let history = model
+        .fit(
+            &xs,
+            &ys,
+            FitOptions {
+                epochs: 6,
+                on_epoch: Some(Box::new(move |epoch, loss, accuracy| {
+                    log.borrow_mut().push((epoch, loss, accuracy));
+                })),
+                ..FitOptions::default()
+            },
+        )
+        .unwrap();
+
+    let seen = seen.borrow();
+    assert_eq!(
+        seen.iter().map(|s| s.0).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4, 5]
+    );
+    for ((_, loss, accuracy), h) in seen.iter().zip(&history) {
+        assert_eq!(loss, h);
+        assert!((0.0..=1.0).contains(accuracy));
+    }
+}
+
+#[test]
+fn seeded_fit_is_bit_for_bit_reproducible() {
+    use rustygrad::{FitOptions, MLP};
+
+    let run = || {
+        let xs: Vec<Vec<f64>> = (0..12)
+            .map(|i| vec![(i as f64 * 0.7).sin(), (i as f64 * 0.3).cos()])
+            .collect();
+        let ys: Vec<f64> = xs.iter().map(|x| (x[0] - x[1]).signum()).collect();
+        let model = MLP::seeded(2, vec![4, 4, 1], 7);
+        let opts = FitOptions {
+            epochs: 5,
+            batch_size: 4,
+            shuffle_seed: Some(3),
+            ..FitOptions::default()
+        };
+        let history = model.fit(&xs, &ys, opts).unwrap();
+        let params: Vec<u64> = model
+            .parameters()
+            .iter()
+            .map(|p| p.borrow().data.to_bits())
+            .collect();
+        (
+            history.iter().map(|l| l.to_bits()).collect::<Vec<_>>(),
+            params,
+        )
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn fit_accepts_any_scheduler() {
+    use rustygrad::{FitOptions, LearningRate, Warmup, MLP};
+
+    let model = MLP::seeded(2, vec![1], 3);
+    let before: Vec<f64> = model.parameters().iter().map(|p| p.borrow().data).collect();
+    // Small inputs keep the hinge loss active, so any nonzero rate moves the weights
+    let (xs, ys) = (vec![vec![0.1, -0.1]], vec![1.0]);
+    let run = |epochs| {
+        model
+            .fit(
+                &xs,
+                &ys,
+                FitOptions {
+                    epochs,
+                    learning_rate: Box::new(Warmup {
+                        inner: LearningRate::Constant(0.1),
+                        warmup_steps: 2,
+                    }),
+                    ..FitOptions::default()
+                },
+            )
+            .unwrap();
+        model
+            .parameters()
+            .iter()
+            .map(|p| p.borrow().data)
+            .collect::<Vec<f64>>()
+    };
+
+    // The first warmup epoch has a zero rate
+    assert_eq!(run(1), before);
+    assert_ne!(run(2), before);
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "std")]
+
+use rustygrad::tuning::{random_search, SearchSpace};
+
+#[test]
+fn random_search_finds_minimum_of_parabola() {
+    let space = SearchSpace::new(42).uniform("x", -5.0, 5.0);
+    let (best, loss) = random_search(&space, 500, |h| (h.get("x") - 1.5).powi(2));
+    assert!((best.get("x") - 1.5).abs() < 0.05);
+    assert_eq!(loss, (best.get("x") - 1.5).powi(2));
+
+    // Same seed, same answer
+    let (again, _) = random_search(&space, 500, |h| (h.get("x") - 1.5).powi(2));
+    assert_eq!(best, again);
+}
+
+#[test]
+fn random_search_samples_within_space() {
+    let space = SearchSpace::new(1)
+        .log_uniform("lr", 1e-4, 1e-1)
+        .choice("width", vec![8.0, 16.0, 32.0]);
+    let (best, _) = random_search(&space, 50, |h| {
+        let lr = h.get("lr");
+        assert!((1e-4..=1e-1).contains(&lr));
+        assert!([8.0, 16.0, 32.0].contains(&h.get("width")));
+        (lr.log10() + 2.0).abs() + (h.get("width") - 16.0).abs()
+    });
+    assert_eq!(best.get("width"), 16.0);
+}
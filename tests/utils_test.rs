@@ -0,0 +1,116 @@
+#![cfg(feature = "std")]
+
+use rustygrad::shuffle_in_unison;
+
+#[test]
+fn shuffle_in_unison_keeps_pairs() {
+    // Tag each row so its label can be recomputed from its features
+    let mut xs: Vec<Vec<f64>> = (0..50).map(|i| vec![i as f64, -(i as f64)]).collect();
+    let mut ys: Vec<f64> = (0..50).map(|i| i as f64 * 10.0).collect();
+
+    shuffle_in_unison(&mut xs, &mut ys, 42);
+
+    assert!(xs.iter().enumerate().any(|(i, x)| x[0] != i as f64));
+    for (x, y) in xs.iter().zip(&ys) {
+        assert_eq!(x[0] * 10.0, *y);
+        assert_eq!(x[1], -x[0]);
+    }
+
+    // Same seed, same permutation
+    let mut xs2: Vec<Vec<f64>> = (0..50).map(|i| vec![i as f64, -(i as f64)]).collect();
+    let mut ys2: Vec<f64> = (0..50).map(|i| i as f64 * 10.0).collect();
+    shuffle_in_unison(&mut xs2, &mut ys2, 42);
+    assert_eq!(xs, xs2);
+    assert_eq!(ys, ys2);
+}
+
+#[test]
+fn subsample_is_sized_and_reproducible() {
+    use rustygrad::subsample;
+
+    let xs: Vec<Vec<f64>> = (0..40).map(|i| vec![i as f64]).collect();
+    let ys: Vec<f64> = (0..40).map(|i| i as f64 * 2.0).collect();
+
+    let (sx, sy) = subsample(&xs, &ys, 0.25, 9);
+    assert_eq!(sx.len(), 10);
+    assert_eq!(sy.len(), 10);
+    assert!(sx.iter().zip(&sy).all(|(x, y)| x[0] * 2.0 == *y));
+    assert!(sx.windows(2).all(|w| w[0][0] < w[1][0]));
+
+    assert_eq!(subsample(&xs, &ys, 0.25, 9), (sx.clone(), sy));
+    assert_ne!(subsample(&xs, &ys, 0.25, 10).0, sx);
+    assert_eq!(subsample(&xs, &ys, 1.0, 9).0, xs);
+}
+
+#[test]
+fn jitter_with_zero_stddev_is_noop() {
+    use rustygrad::jitter;
+
+    let original: Vec<Vec<f64>> = (0..10).map(|i| vec![i as f64, 0.5 * i as f64]).collect();
+    let mut xs = original.clone();
+    jitter(&mut xs, 0.0, 3);
+    assert_eq!(xs, original);
+
+    jitter(&mut xs, 0.1, 3);
+    assert_ne!(xs, original);
+}
+
+#[test]
+fn rotate2d_full_turn_returns_original() {
+    use rustygrad::rotate2d;
+
+    let original = vec![vec![1.0, 0.0], vec![-2.0, 3.5], vec![0.25, -0.75]];
+    let mut xs = original.clone();
+    rotate2d(&mut xs, std::f64::consts::FRAC_PI_2);
+    assert!((xs[0][0]).abs() < 1e-12 && (xs[0][1] - 1.0).abs() < 1e-12);
+
+    rotate2d(&mut xs, 1.5 * std::f64::consts::PI);
+    for (x, o) in xs.iter().zip(&original) {
+        assert!((x[0] - o[0]).abs() < 1e-9);
+        assert!((x[1] - o[1]).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn mixup_with_degenerate_lambda_returns_originals() {
+    use rustygrad::mixup;
+
+    let xs: Vec<Vec<f64>> = (0..20).map(|i| vec![i as f64, 2.0 * i as f64]).collect();
+    let ys: Vec<f64> = (0..20)
+        .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+        .collect();
+
+    // alpha = 0 disables mixing: λ = 1
+    let (mx, my) = mixup(&xs, &ys, 0.0, 7);
+    assert_eq!(mx, xs);
+    assert_eq!(my, ys);
+
+    // A tiny alpha pushes λ to 0 or 1, so every row is (nearly) some original
+    let (mx, my) = mixup(&xs, &ys, 1e-4, 7);
+    for (x, y) in mx.iter().zip(&my) {
+        let k = x[0].round() as usize;
+        assert!((x[0] - xs[k][0]).abs() < 1e-6 && (x[1] - xs[k][1]).abs() < 1e-6);
+        assert!((y - ys[k]).abs() < 1e-6);
+    }
+
+    // A moderate alpha blends labels into the interior
+    let (_, my) = mixup(&xs, &ys, 1.0, 7);
+    assert!(my.iter().all(|y| (-1.0..=1.0).contains(y)));
+    assert!(my.iter().any(|y| y.abs() < 1.0));
+}
+
+#[test]
+fn polynomial_features_degree_two() {
+    use rustygrad::polynomial_features;
+
+    let expanded = polynomial_features(&[vec![2.0, 3.0], vec![-1.0, 0.5]], 2);
+    assert_eq!(expanded[0], vec![2.0, 3.0, 4.0, 6.0, 9.0]);
+    assert_eq!(expanded[1], vec![-1.0, 0.5, 1.0, -0.5, 0.25]);
+
+    // 3 features up to degree 3: 3 + 6 + 10 monomials
+    assert_eq!(polynomial_features(&[vec![1.0; 3]], 3)[0].len(), 19);
+    assert_eq!(
+        polynomial_features(&[vec![1.0, 2.0]], 1),
+        vec![vec![1.0, 2.0]]
+    );
+}
@@ -42,3 +42,584 @@ fn value_example() {
     assert!((138.8338 - a.borrow().grad).abs() < EPS);
     assert!((645.5773 - b.borrow().grad).abs() < EPS);
 }
+
+#[test]
+fn checked_div_rejects_zero_denominator() {
+    let a = Value::from(3.0);
+    assert!(a.checked_div(&Value::from(0.0)).is_none());
+
+    let q = a.checked_div(&Value::from(2.0)).unwrap();
+    assert!((1.5 - q.borrow().data).abs() < EPS);
+}
+
+#[test]
+fn division_by_zero_does_not_poison_backward() {
+    let a = Value::from(3.0);
+    let b = Value::from(0.0);
+    let c = Value::from(2.0);
+
+    // The quotient is infinite but unused downstream (multiplied by zero)
+    let q = &a / &b;
+    assert!(q.borrow().data.is_infinite());
+    let g = &q * 0.0 + &c * &c;
+    g.backward();
+
+    assert_eq!(a.borrow().grad, 0.0);
+    assert_eq!(b.borrow().grad, 0.0);
+    assert!((4.0 - c.borrow().grad).abs() < EPS);
+}
+
+#[test]
+fn threshold_branches() {
+    // Above the threshold the input passes through with gradient 1
+    let a = Value::from(2.0);
+    let t = a.threshold(1.0, -5.0);
+    t.backward();
+    assert!((2.0 - t.borrow().data).abs() < EPS);
+    assert!((1.0 - a.borrow().grad).abs() < EPS);
+
+    // Below the threshold the replacement value is emitted with gradient 0
+    let b = Value::from(0.5);
+    let t = b.threshold(1.0, -5.0);
+    t.backward();
+    assert!((-5.0 - t.borrow().data).abs() < EPS);
+    assert_eq!(b.borrow().grad, 0.0);
+
+    // At the boundary the input is not strictly greater, so it is replaced
+    let c = Value::from(1.0);
+    let t = c.threshold(1.0, -5.0);
+    t.backward();
+    assert!((-5.0 - t.borrow().data).abs() < EPS);
+    assert_eq!(c.borrow().grad, 0.0);
+}
+
+#[test]
+fn expr_string_uses_labels() {
+    let a = Value::from(1.0).with_label("a");
+    let b = Value::from(2.0).with_label("b");
+    let c = Value::from(3.0).with_label("c");
+
+    assert_eq!(((&a + &b) * &c).expr_string(), "(a + b) * c");
+    assert_eq!(((&a + &b) * &c).pow(2.0).expr_string(), "((a + b) * c)^2");
+    assert_eq!((&a * 4.0).relu().expr_string(), "ReLU(a * 4)");
+}
+
+#[test]
+fn expr_string_handles_shared_subexpressions() {
+    // x is reused by every level, so it is expanded once per path
+    let mut x = Value::from(1.0).with_label("x");
+    for _ in 0..10 {
+        x = &x + &x;
+    }
+    assert_eq!(x.expr_string().matches('x').count(), 1024);
+}
+
+#[test]
+fn deep_copy_is_independent() {
+    let a = Value::from(2.0);
+    let b = &a * 3.0;
+    b.backward();
+
+    let shallow = b.clone();
+    let deep = b.deep_copy();
+    assert_eq!(deep.borrow().data, 6.0);
+    assert_eq!(deep.borrow().grad, 0.0);
+    assert!(deep.borrow()._prev.is_empty());
+    assert!(deep != b);
+
+    deep.borrow_mut().data = 100.0;
+    assert_eq!(b.borrow().data, 6.0);
+
+    shallow.borrow_mut().data = 100.0;
+    assert_eq!(b.borrow().data, 100.0);
+}
+
+#[test]
+fn validate_accepts_dags_and_reports_cycles() {
+    let a = Value::from(1.0);
+    let b = Value::from(2.0);
+    let shared = &a * &b;
+    let g = (&shared + &shared).relu();
+    assert!(g.validate().is_ok());
+
+    // Wire the output back into one of its own inputs
+    a.borrow_mut()._prev.push(g.clone());
+    a.borrow_mut()._op = Some(String::from("+"));
+    let err = g.validate().unwrap_err();
+    assert!(err.contains("cycle"));
+
+    // Break the cycle again so the nodes can be dropped
+    a.borrow_mut()._prev.clear();
+}
+
+#[test]
+fn approx_eq_accepts_near_and_rejects_far() {
+    let a = Value::from(1.0) / 3.0;
+    assert!(a.approx_eq(0.3333, EPS));
+    assert!(!a.approx_eq(0.34, EPS));
+    assert!(!a.approx_eq(-0.3333, EPS));
+
+    rustygrad::assert_value_approx!(a, 1.0 / 3.0);
+    rustygrad::assert_value_approx!(&a * 3.0, 1.0, 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "is not within")]
+fn assert_value_approx_panics_when_far() {
+    rustygrad::assert_value_approx!(Value::from(1.0), 1.1, 0.01);
+}
+
+#[test]
+fn graph_size_counts_distinct_nodes() {
+    let a = Value::from(1.0);
+    let b = Value::from(2.0);
+    assert_eq!(a.graph_size(), 1);
+
+    // a, b, a*b, and the sum reusing a*b twice
+    let ab = &a * &b;
+    assert_eq!((&ab + &ab).graph_size(), 4);
+}
+
+#[test]
+fn relu_with_zero_grad_policies() {
+    use rustygrad::ReluZeroGrad;
+
+    for (policy, expected) in [
+        (ReluZeroGrad::Zero, 0.0),
+        (ReluZeroGrad::Half, 0.5),
+        (ReluZeroGrad::PassThrough, 1.0),
+    ] {
+        let x = Value::from(0.0);
+        let y = x.relu_with(policy);
+        y.backward();
+        assert_eq!(y.borrow().data, 0.0);
+        assert_eq!(x.borrow().grad, expected);
+
+        // Away from zero every policy agrees with relu
+        let x = Value::from(-1.0);
+        x.relu_with(policy).backward();
+        assert_eq!(x.borrow().grad, 0.0);
+        let x = Value::from(2.0);
+        x.relu_with(policy).backward();
+        assert_eq!(x.borrow().grad, 1.0);
+    }
+}
+
+#[test]
+fn exp_and_log_gradients() {
+    let x = Value::from(1.5);
+    let y = x.exp().log();
+    y.backward();
+    assert!((1.5 - y.borrow().data).abs() < EPS);
+    assert!((1.0 - x.borrow().grad).abs() < EPS);
+
+    let x = Value::from(2.0);
+    x.log().backward();
+    assert!((0.5 - x.borrow().grad).abs() < EPS);
+}
+
+#[test]
+fn scalar_compound_assignment() {
+    let a = Value::from(3.0);
+    let mut c = a.clone();
+    c += 1.0;
+    assert_eq!(c.borrow().data, 4.0);
+    c *= 2.0;
+    assert_eq!(c.borrow().data, 8.0);
+
+    // c is a new node downstream of a, which is left untouched
+    assert_eq!(a.borrow().data, 3.0);
+    c.backward();
+    assert_eq!(a.borrow().grad, 2.0);
+}
+
+#[test]
+fn div_gradients_match_pow_path() {
+    let (a, b) = (Value::from(3.0), Value::from(-1.5));
+    let q = a.div(&b);
+    q.backward();
+
+    let (a2, b2) = (Value::from(3.0), Value::from(-1.5));
+    let q2 = &a2 * b2.pow(-1.0);
+    q2.backward();
+
+    assert!((q.borrow().data - q2.borrow().data).abs() < EPS);
+    assert!((a.borrow().grad - a2.borrow().grad).abs() < EPS);
+    assert!((b.borrow().grad - b2.borrow().grad).abs() < EPS);
+}
+
+#[test]
+fn div_gradient_is_finite_for_tiny_denominators() {
+    // b² underflows here, so the pow-based gradient overflows to -inf
+    let (a, b) = (Value::from(1e-160), Value::from(1e-160));
+    a.div(&b).backward();
+    assert!((b.borrow().grad + 1e160).abs() < 1e150);
+
+    let (a2, b2) = (Value::from(1e-160), Value::from(1e-160));
+    (&a2 * b2.pow(-1.0)).backward();
+    assert!(b2.borrow().grad.is_infinite());
+}
+
+#[test]
+fn heaviside_steps_without_gradient() {
+    for (x, expected) in [(-2.0, 0.0), (0.0, 0.25), (3.0, 1.0)] {
+        let a = Value::from(x);
+        let h = a.heaviside(0.25);
+        assert_eq!(h.borrow().data, expected);
+
+        (&h * &a + &a).backward();
+        // Only the direct `+ a` and `h * a` paths contribute, never the step itself
+        assert_eq!(a.borrow().grad, 1.0 + expected);
+    }
+}
+
+#[test]
+fn fake_quant_snaps_and_passes_gradient_in_range() {
+    // 4 bits with scale 0.5 and zero point 8 covers [-4.0, 3.5]
+    for (x, expected) in [(0.3, 0.5), (-1.1, -1.0), (2.74, 2.5)] {
+        let a = Value::from(x);
+        let q = a.fake_quant(0.5, 8.0, 4);
+        assert!((q.borrow().data - expected).abs() < EPS);
+
+        (&q * 3.0).backward();
+        assert_eq!(a.borrow().grad, 3.0);
+    }
+
+    // Clamped inputs saturate and stop the gradient
+    for (x, expected) in [(10.0, 3.5), (-10.0, -4.0)] {
+        let a = Value::from(x);
+        let q = a.fake_quant(0.5, 8.0, 4);
+        assert!((q.borrow().data - expected).abs() < EPS);
+        q.backward();
+        assert_eq!(a.borrow().grad, 0.0);
+    }
+}
+
+#[test]
+fn fake_quant_passes_gradient_when_rounding_lands_on_a_bound() {
+    // Under half a step past each bound: rounds onto q = 15 and q = 0, so
+    // nothing is clamped and the gradient must still flow
+    for (x, expected) in [(3.7, 3.5), (-4.2, -4.0)] {
+        let a = Value::from(x);
+        let q = a.fake_quant(0.5, 8.0, 4);
+        assert!((q.borrow().data - expected).abs() < EPS);
+        q.backward();
+        assert_eq!(a.borrow().grad, 1.0);
+    }
+}
+
+#[test]
+fn backward_with_scales_all_gradients() {
+    let build = || {
+        let a = Value::from(-4.0);
+        let b = Value::from(2.0);
+        let g = (&a * &b + b.pow(3.0)).relu() + (&a / &b).exp();
+        (a, b, g)
+    };
+
+    let (a1, b1, g1) = build();
+    g1.backward();
+    let (a2, b2, g2) = build();
+    g2.backward_with(2.0);
+
+    assert!((2.0 * a1.borrow().grad - a2.borrow().grad).abs() < EPS);
+    assert!((2.0 * b1.borrow().grad - b2.borrow().grad).abs() < EPS);
+    assert_eq!(g2.borrow().grad, 2.0);
+}
+
+#[test]
+fn from_op_builds_custom_square() {
+    use rustygrad::ValueData;
+
+    fn square(x: &Value) -> Value {
+        Value::from_op(
+            x.borrow().data.powi(2),
+            vec![x.clone()],
+            "square",
+            |value: &ValueData| {
+                let x = value._prev[0].borrow().data;
+                value._prev[0].borrow_mut().grad += 2.0 * x * value.grad;
+            },
+        )
+    }
+
+    let x = Value::from(3.0);
+    let y = square(&x) + &x;
+    y.backward();
+    assert_eq!(y.borrow().data, 12.0);
+    assert!((x.borrow().grad - 7.0).abs() < EPS);
+}
+
+#[test]
+fn set_leaf_reuses_graph() {
+    let x = Value::from(1.0);
+    let w = Value::from(-3.0);
+    let y = (&x * &w + 2.0).relu().pow(2.0) + (&x / &w).exp();
+
+    for sample in [-2.0, 0.5, -1.5] {
+        x.set_leaf(sample);
+        y.recompute().unwrap();
+        y.backward();
+
+        let fresh_x = Value::from(sample);
+        let fresh_w = Value::from(-3.0);
+        let fresh = (&fresh_x * &fresh_w + 2.0).relu().pow(2.0) + (&fresh_x / &fresh_w).exp();
+        fresh.backward();
+
+        assert!((y.borrow().data - fresh.borrow().data).abs() < EPS);
+        assert!((x.borrow().grad - fresh_x.borrow().grad).abs() < EPS);
+        assert!((w.borrow().grad - fresh_w.borrow().grad).abs() < EPS);
+    }
+}
+
+#[test]
+fn backward_and_free_releases_graph() {
+    use std::rc::Rc;
+
+    let build = |x: &Value| {
+        let hidden = x * 2.0;
+        let out = (&hidden + 1.0).relu();
+        (hidden, out)
+    };
+
+    let x = Value::from(3.0);
+    let (hidden, out) = build(&x);
+    out.backward();
+    // Held by `hidden` and by the `+` node that consumes it
+    assert_eq!(Rc::strong_count(&hidden), 2);
+    assert!((x.borrow().grad - 2.0).abs() < EPS);
+
+    let x = Value::from(3.0);
+    let (hidden, out) = build(&x);
+    out.backward_and_free();
+    assert_eq!(Rc::strong_count(&hidden), 1);
+    assert_eq!(Rc::strong_count(&x), 1);
+    assert!((x.borrow().grad - 2.0).abs() < EPS);
+    assert!((out.borrow().data - 7.0).abs() < EPS);
+    assert!(out.borrow()._prev.is_empty());
+}
+
+// Compares bit-for-bit with std's `f64` methods, which the no_std build replaces
+#[cfg(feature = "std")]
+#[test]
+fn inverse_trig_derivatives_match_finite_differences() {
+    fn check(op: fn(&Value) -> Value, f: fn(f64) -> f64) {
+        let h = 1e-6;
+        for x0 in [-0.7, -0.2, 0.3, 0.9] {
+            let x = Value::from(x0);
+            let y = op(&x);
+            y.backward();
+            assert_eq!(y.borrow().data, f(x0));
+            let numeric = (f(x0 + h) - f(x0 - h)) / (2.0 * h);
+            assert!((x.borrow().grad - numeric).abs() < EPS);
+        }
+    }
+    check(Value::atan, f64::atan);
+    check(Value::asin, f64::asin);
+    check(Value::acos, f64::acos);
+
+    // The domain edge yields a large but finite gradient
+    let x = Value::from(1.0);
+    x.asin().backward();
+    assert!(x.borrow().grad.is_finite());
+}
+
+#[test]
+fn select_with_full_mask_picks_if_true() {
+    let mask = Value::from(1.0);
+    let a = Value::from(3.0);
+    let b = Value::from(-5.0);
+    let out = Value::select(&mask, &a, &b);
+    out.backward();
+
+    assert_eq!(out.borrow().data, 3.0);
+    assert_eq!(a.borrow().grad, 1.0);
+    assert_eq!(b.borrow().grad, 0.0);
+    assert_eq!(mask.borrow().grad, 8.0);
+
+    let half = Value::from(0.25);
+    let out = Value::select(&half, &a, &b);
+    assert!((out.borrow().data - (0.75 - 3.75)).abs() < EPS);
+}
+
+#[test]
+fn stop_gradient_blocks_backward() {
+    let a = Value::from(2.0);
+    let b = Value::from(3.0);
+    let inner = &a * &b;
+    let stopped = inner.stop_gradient();
+    let out = &stopped * &a + &b;
+    out.backward();
+
+    assert_eq!(stopped.borrow().data, 6.0);
+    assert_eq!(out.borrow().data, 15.0);
+    assert_eq!(stopped.borrow().grad, 2.0);
+    assert_eq!(inner.borrow().grad, 0.0);
+    // Only the direct paths around the stop point contribute
+    assert_eq!(a.borrow().grad, 6.0);
+    assert_eq!(b.borrow().grad, 1.0);
+}
+
+#[test]
+fn sum_of_borrowed_values_matches_owned() {
+    let vs: Vec<Value> = [1.5, -2.0, 4.25].iter().map(|&v| Value::from(v)).collect();
+    let borrowed: Value = vs.iter().sum();
+    let owned: Value = vs.clone().into_iter().sum();
+    assert_eq!(borrowed.borrow().data, owned.borrow().data);
+
+    borrowed.backward();
+    assert!(vs.iter().all(|v| v.borrow().grad == 1.0));
+}
+
+#[test]
+fn clone_graph_is_independent() {
+    let a = Value::from(2.0);
+    let b = Value::from(-1.5);
+    let shared = &a * &b;
+    let out = (&shared + &shared).relu() + shared.exp();
+
+    let copy = out.clone_graph();
+    assert_eq!(copy.borrow().data, out.borrow().data);
+    assert_eq!(copy.graph_size(), out.graph_size());
+    assert_eq!(copy.expr_string(), out.expr_string());
+
+    copy.backward();
+    assert_eq!(a.borrow().grad, 0.0);
+    assert_eq!(shared.borrow().grad, 0.0);
+
+    out.backward();
+    assert!((a.borrow().grad - b.borrow().data * shared.borrow().data.exp()).abs() < EPS);
+}
+
+#[test]
+fn powf_matches_pow() {
+    for power in [-1.5, 0.5, 2.0, 3.0] {
+        let a = Value::from(1.7);
+        let b = Value::from(1.7);
+        let pa = a.pow(power);
+        let pb = b.powf(power);
+        pa.backward();
+        pb.backward();
+        assert_eq!(pa.borrow().data, pb.borrow().data);
+        assert_eq!(a.borrow().grad, b.borrow().grad);
+    }
+}
+
+#[test]
+fn piecewise_linear_replicates_identity_and_relu() {
+    for x0 in [-3.0, -0.4, 0.7, 5.0] {
+        let x = Value::from(x0);
+        let y = x.piecewise_linear(&[-1.0, 1.0], &[-1.0, 1.0]);
+        y.backward();
+        assert!((y.borrow().data - x0).abs() < EPS);
+        assert_eq!(x.borrow().grad, 1.0);
+    }
+
+    for x0 in [-2.5, -0.3, 0.0, 0.3, 4.0] {
+        let a = Value::from(x0);
+        let b = Value::from(x0);
+        let pwl = a.piecewise_linear(&[-1.0, 0.0, 1.0], &[0.0, 0.0, 1.0]);
+        let relu = b.relu();
+        pwl.backward();
+        relu.backward();
+        assert!((pwl.borrow().data - relu.borrow().data).abs() < EPS);
+        assert_eq!(a.borrow().grad, b.borrow().grad);
+    }
+}
+
+#[test]
+fn fma_matches_multiply_add() {
+    let (a, b, c) = (Value::from(1.5), Value::from(-2.0), Value::from(0.75));
+    let fused = Value::fma(&a, &b, &c).pow(2.0);
+    fused.backward();
+
+    let (a2, b2, c2) = (Value::from(1.5), Value::from(-2.0), Value::from(0.75));
+    let unfused = (&a2 * &b2 + &c2).pow(2.0);
+    unfused.backward();
+
+    assert_eq!(fused.borrow().data, unfused.borrow().data);
+    for (x, y) in [(&a, &a2), (&b, &b2), (&c, &c2)] {
+        assert!((x.borrow().grad - y.borrow().grad).abs() < EPS);
+    }
+    assert_eq!(fused.graph_size(), unfused.graph_size() - 1);
+}
+
+#[test]
+fn clip_grad_clamps_what_flows_to_children() {
+    let x = Value::from(2.0);
+    let clipped = (&x * 3.0).clip_grad(0.5);
+    let out = &clipped * 10.0;
+    out.backward();
+
+    assert_eq!(clipped.borrow().data, 6.0);
+    assert_eq!(clipped.borrow().grad, 10.0);
+    // 0.5 reaches `x * 3`, then the chain rule scales it by 3
+    assert!((x.borrow().grad - 1.5).abs() < EPS);
+
+    // Gradients already within bounds pass unchanged
+    let y = Value::from(2.0);
+    let small = (&y * 3.0).clip_grad(0.5) * 0.2;
+    small.backward();
+    assert!((y.borrow().grad - 0.6).abs() < EPS);
+}
+
+#[test]
+fn from_nary_sum_node() {
+    use rustygrad::ValueData;
+
+    fn sum_backward(value: &ValueData) {
+        for p in &value._prev {
+            p.borrow_mut().grad += value.grad;
+        }
+    }
+    let nary_sum = |xs: &[Value]| {
+        let data = xs.iter().map(|x| x.borrow().data).sum();
+        Value::from_nary(data, xs.to_vec(), "sum", sum_backward)
+    };
+
+    let xs: Vec<Value> = (0..6).map(|i| Value::from(1.0 + i as f64 * 0.5)).collect();
+    let total = nary_sum(&xs);
+    let out = &total * &xs[0] + &xs[5];
+    out.backward();
+
+    assert_eq!(total.borrow().data, 13.5);
+    assert_eq!(total.borrow()._prev.len(), 6);
+    // d(out)/d(xs[i]) = xs[0] from the sum, plus the direct uses of xs[0] and xs[5]
+    assert!((xs[0].borrow().grad - (1.0 + 13.5)).abs() < EPS);
+    for x in &xs[1..5] {
+        assert!((x.borrow().grad - 1.0).abs() < EPS);
+    }
+    assert!((xs[5].borrow().grad - 2.0).abs() < EPS);
+
+    // One node instead of a chain of five `+`
+    assert_eq!(total.graph_size(), 7);
+    assert_eq!(xs.iter().sum::<Value>().graph_size(), 11);
+}
+
+#[test]
+fn nodes_get_distinct_ids_and_dedup_by_identity() {
+    use std::collections::HashSet;
+
+    let a = Value::from(1.0);
+    let b = Value::from(1.0);
+    let c = &a * &b;
+    let ids: HashSet<u64> = [&a, &b, &c].iter().map(|v| v.borrow().id).collect();
+    assert_eq!(ids.len(), 3);
+    // Equal data does not make equal nodes, but a shallow clone is the same node
+    assert_ne!(a, b);
+    assert_eq!(a, a.clone());
+    assert_eq!(a.clone().borrow().id, a.borrow().id);
+
+    // Ids from other threads do not collide either
+    let other: Vec<u64> =
+        std::thread::spawn(|| (0..100).map(|_| Value::from(0.0).borrow().id).collect())
+            .join()
+            .unwrap();
+    assert!(other.iter().all(|id| !ids.contains(id)));
+
+    // `a` feeds the graph twice but is visited and updated once per use
+    let d = &c + &a;
+    assert_eq!(d.graph_size(), 4);
+    d.backward();
+    assert!((a.borrow().grad - 2.0).abs() < EPS);
+}
@@ -0,0 +1,167 @@
+#![cfg(feature = "std")]
+
+use rustygrad::{vecops, Value};
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn add_scalar_accumulates_bias_grad() {
+    let v: Vec<Value> = (0..5).map(|i| Value::from(i as f64)).collect();
+    let bias = Value::from(0.5);
+
+    let out = vecops::add_scalar(&v, &bias);
+    assert_eq!(out.len(), 5);
+    assert_eq!(out[3].borrow().data, 3.5);
+
+    // Upstream grad of 2 into each element
+    let total = out.into_iter().sum::<Value>() * 2.0;
+    total.backward();
+    assert_eq!(bias.borrow().grad, 5.0 * 2.0);
+    assert!(v.iter().all(|vi| vi.borrow().grad == 2.0));
+}
+
+#[test]
+fn max_reduce_routes_grad_to_argmax() {
+    let vs: Vec<Value> = [0.5, 3.0, -1.0, 2.0]
+        .iter()
+        .map(|&v| Value::from(v))
+        .collect();
+    let m = vecops::max_reduce(&vs);
+    assert_eq!(m.borrow().data, 3.0);
+
+    (&m * 4.0).backward();
+    let grads: Vec<f64> = vs.iter().map(|v| v.borrow().grad).collect();
+    assert_eq!(grads, vec![0.0, 4.0, 0.0, 0.0]);
+}
+
+#[test]
+fn sum_axis_reduces_both_dimensions() {
+    let batch: Vec<Vec<Value>> = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]
+        .iter()
+        .map(|row| row.iter().map(|&v| Value::from(v)).collect())
+        .collect();
+
+    let data = |vs: Vec<Value>| -> Vec<f64> { vs.iter().map(|v| v.borrow().data).collect() };
+    assert_eq!(data(vecops::sum_axis(&batch, 0)), vec![5.0, 7.0, 9.0]);
+    assert_eq!(data(vecops::sum_axis(&batch, 1)), vec![6.0, 15.0]);
+
+    // Each element feeds exactly one column sum
+    let cols = vecops::sum_axis(&batch, 0);
+    (&cols[1] * 3.0).backward();
+    assert_eq!(batch[0][1].borrow().grad, 3.0);
+    assert_eq!(batch[1][1].borrow().grad, 3.0);
+    assert_eq!(batch[0][0].borrow().grad, 0.0);
+}
+
+#[test]
+fn soft_histogram_conserves_mass() {
+    let vs: Vec<Value> = [-1.2, -0.1, 0.0, 0.4, 2.5, 40.0]
+        .iter()
+        .map(|&v| Value::from(v))
+        .collect();
+    let counts = vecops::soft_histogram(&vs, &[-1.0, 0.0, 1.0, 2.0], 0.5);
+    assert_eq!(counts.len(), 4);
+
+    let total: Value = counts.iter().sum();
+    assert!((total.borrow().data - vs.len() as f64).abs() < EPS);
+    // The outlier lands (almost) entirely in the last bin
+    assert!(counts[3].borrow().data > 1.0);
+
+    counts[1].backward();
+    assert!(vs.iter().all(|v| v.borrow().grad.is_finite()));
+    // Moving -0.1 right brings it closer to the center bin
+    assert!(vs[1].borrow().grad > 0.0);
+}
+
+#[test]
+fn topk_routes_gradient_to_selected() {
+    let data = [0.5, 3.0, -1.0, 2.0, 7.5, 0.0];
+    let vs: Vec<Value> = data.iter().map(|&d| Value::from(d)).collect();
+
+    let top = vecops::topk(&vs, 3);
+    let picked: Vec<f64> = top.iter().map(|v| v.borrow().data).collect();
+    assert_eq!(picked, vec![7.5, 3.0, 2.0]);
+
+    let total: Value = top.iter().map(|v| v * 2.0).sum();
+    total.backward();
+    let grads: Vec<f64> = vs.iter().map(|v| v.borrow().grad).collect();
+    assert_eq!(grads, vec![0.0, 2.0, 0.0, 2.0, 2.0, 0.0]);
+
+    assert_eq!(vecops::topk(&vs, 10).len(), 6);
+}
+
+#[test]
+fn cosine_similarity_values_and_gradients() {
+    use rustygrad::gradcheck;
+
+    let vals = |d: &[f64]| d.iter().map(|&x| Value::from(x)).collect::<Vec<Value>>();
+    let same = vecops::cosine_similarity(&vals(&[1.0, -2.0, 0.5]), &vals(&[1.0, -2.0, 0.5]));
+    assert!((same.borrow().data - 1.0).abs() < EPS);
+    let orthogonal = vecops::cosine_similarity(&vals(&[1.0, 0.0]), &vals(&[0.0, 3.0]));
+    assert!(orthogonal.borrow().data.abs() < EPS);
+    let zero = vecops::cosine_similarity(&vals(&[0.0, 0.0]), &vals(&[1.0, 1.0]));
+    zero.backward();
+    assert_eq!(zero.borrow().data, 0.0);
+
+    let checks = gradcheck::grad_check(
+        |p| vecops::cosine_similarity(&p[..3], &p[3..]),
+        &[0.3, -1.2, 0.8, 1.5, 0.4, -0.6],
+        1e-6,
+    );
+    for (analytic, numeric) in checks {
+        assert!(analytic != 0.0 && (analytic - numeric).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn relu_vec_matches_elementwise_relu() {
+    let data = [-1.5, 0.0, 0.25, 3.0];
+    let a: Vec<Value> = data.iter().map(|&d| Value::from(d)).collect();
+    let b: Vec<Value> = data.iter().map(|&d| Value::from(d)).collect();
+
+    let batched: Value = vecops::relu_vec(&a).iter().map(|v| v * 3.0).sum();
+    let mapped: Value = b.iter().map(|v| v.relu() * 3.0).sum();
+    batched.backward();
+    mapped.backward();
+
+    assert_eq!(batched.borrow().data, mapped.borrow().data);
+    for (x, y) in a.iter().zip(&b) {
+        assert_eq!(x.borrow().grad, y.borrow().grad);
+    }
+
+    let squashed = vecops::tanh_vec(&a);
+    assert!(squashed.iter().all(|v| v.borrow().data.abs() < 1.0));
+    let gated = vecops::sigmoid_vec(&a);
+    assert!((gated[1].borrow().data - 0.5).abs() < EPS);
+}
+
+#[test]
+fn sum_balanced_matches_chain_with_log_depth() {
+    // Longest path from `v` down to a leaf
+    fn depth(v: &Value) -> usize {
+        v.borrow()
+            ._prev
+            .iter()
+            .map(depth)
+            .max()
+            .map_or(0, |d| d + 1)
+    }
+
+    let xs: Vec<Value> = (0..100)
+        .map(|i| Value::from((i as f64 * 0.37).sin()))
+        .collect();
+    let ys: Vec<Value> = xs.iter().map(|x| x.deep_copy()).collect();
+
+    let balanced = vecops::sum_balanced(&xs) * 3.0;
+    let chain = ys.iter().sum::<Value>() * 3.0;
+    balanced.backward();
+    chain.backward();
+
+    assert!((balanced.borrow().data - chain.borrow().data).abs() < EPS);
+    for (x, y) in xs.iter().zip(&ys) {
+        assert!((x.borrow().grad - y.borrow().grad).abs() < EPS);
+    }
+    // ceil(log2(100)) = 7 levels of `+`, plus the final `* 3`
+    assert_eq!(depth(&balanced), 8);
+    assert!(depth(&chain) >= 100);
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+#[cfg(feature = "png")]
+#[test]
+fn save_decision_png_writes_image() {
+    use rustygrad::{viz::save_decision_png, MLP};
+
+    // Score = x, so the image is blue on the left and red on the right
+    let model = MLP::new(2, vec![1]);
+    for (p, w) in model.parameters().iter().zip([0.0, 1.0, 0.0]) {
+        p.borrow_mut().data = w;
+    }
+    let path = std::env::temp_dir().join("rustygrad_decision.png");
+    save_decision_png(&model, 2.0, 16, path.to_str().unwrap()).unwrap();
+
+    let image = image::open(&path).unwrap().to_rgb8();
+    assert_eq!(image.dimensions(), (16, 16));
+    for y in [0, 15] {
+        // x = -2 is the most negative score, x = 0 is neutral
+        assert_eq!(image.get_pixel(0, y).0, [0, 0, 255]);
+        assert_eq!(image.get_pixel(8, y).0, [255, 255, 255]);
+        let right = image.get_pixel(15, y).0;
+        assert_eq!(right[0], 255);
+        assert!(right[1] < 64 && right[1] == right[2]);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn to_latex_renders_common_ops() {
+    use rustygrad::{viz::to_latex, Value};
+
+    let a = Value::from(1.0).with_label("a");
+    let b = Value::from(2.0).with_label("b");
+    let c = Value::from(3.0).with_label("c");
+
+    assert_eq!(
+        to_latex(&((&a + &b) * &c).pow(2.0)),
+        "\\left(\\left(a + b\\right) \\cdot c\\right)^{2}"
+    );
+    assert_eq!(to_latex(&(&a / &b)), "\\frac{a}{b}");
+    assert_eq!(to_latex(&(&a - &b)), "a - b");
+    assert_eq!(
+        to_latex(&(&a * &b).relu()),
+        "\\mathrm{ReLU}\\left(a \\cdot b\\right)"
+    );
+}
+
+#[test]
+fn op_registry_symbols_are_distinct_and_rendered() {
+    use rustygrad::viz::{op_info, to_dot, Arity, OPS};
+    use rustygrad::{vecops, Value};
+    use std::collections::HashSet;
+
+    let symbols: HashSet<&str> = OPS.iter().map(|info| info.symbol).collect();
+    assert_eq!(symbols.len(), OPS.len());
+    assert_eq!(op_info("×").unwrap().arity, Arity::Binary);
+    assert!(op_info("nope").is_none());
+
+    let a = Value::from(1.0).with_label("a");
+    let b = Value::from(2.0).with_label("b");
+    let g = vecops::max_reduce(&[(&a / &b).relu(), a.exp()]);
+    let dot = to_dot(&g);
+
+    assert!(dot.contains("shape=circle label=\"÷\""));
+    assert!(dot.contains("shape=ellipse label=\"ReLU\""));
+    assert!(dot.contains("shape=diamond label=\"max\""));
+    assert!(dot.contains("a | data=1.0000"));
+    // a feeds two ops but is rendered once
+    assert_eq!(dot.matches("a | ").count(), 1);
+}